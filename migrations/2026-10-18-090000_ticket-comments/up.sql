@@ -0,0 +1,10 @@
+-- Your SQL goes here
+create table ticket_comments (
+    id integer primary key not null,
+    ticket_id integer not null references tickets(id),
+    author_id integer not null references users(id),
+    parent_comment_id integer references ticket_comments(id),
+    body text not null,
+    created text not null,
+    last_modified text not null
+);
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+create table ticket_history (
+    id integer primary key not null,
+    ticket_id integer not null,
+    actor_id integer not null references users(id),
+    field text not null,
+    old_value text,
+    new_value text,
+    changed text not null
+);
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+update tickets set labels = (
+    select json_group_array(labels.name)
+    from json_each(tickets.labels)
+    join labels on labels.id = json_each.value
+);
+
+drop table labels;
@@ -0,0 +1,22 @@
+-- Your SQL goes here
+create table labels (
+    id integer primary key not null,
+    name text not null unique,
+    color text not null,
+    description text
+);
+
+-- the labels that used to be hard-coded, so existing tickets keep theirs
+insert into labels (id, name, color, description) values
+    (1, 'Feature', '#a2eeef', 'New feature or request'),
+    (2, 'Bug', '#d73a4a', 'Something isn''t working'),
+    (3, 'WontFix', '#ffffff', 'This will not be worked on'),
+    (4, 'Done', '#0e8a16', 'Work on this is finished'),
+    (5, 'InProgress', '#fbca04', 'Someone is working on this');
+
+-- tickets reference labels by id from now on
+update tickets set labels = (
+    select json_group_array(labels.id)
+    from json_each(tickets.labels)
+    join labels on labels.name = json_each.value
+);
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+-- only Open and Closed exist without this migration
+update tickets set status = 'Closed' where status in (select name from statuses where is_closed);
+update tickets set status = 'Open' where status != 'Closed';
+
+drop table status_transitions;
+drop table statuses;
@@ -0,0 +1,41 @@
+-- Your SQL goes here
+create table statuses (
+    id integer primary key not null,
+    name text not null unique,
+    is_closed boolean not null default false
+);
+
+create table status_transitions (
+    id integer primary key not null,
+    from_status text not null references statuses(name),
+    to_status text not null references statuses(name),
+    unique (from_status, to_status)
+);
+
+insert into statuses (id, name, is_closed) values
+    (1, 'Open', false),
+    (2, 'Triage', false),
+    (3, 'In Review', false),
+    (4, 'Blocked', false),
+    (5, 'Reopened', false),
+    (6, 'Closed', true);
+
+insert into status_transitions (from_status, to_status) values
+    ('Open', 'Triage'),
+    ('Open', 'In Review'),
+    ('Open', 'Blocked'),
+    ('Open', 'Closed'),
+    ('Triage', 'Open'),
+    ('Triage', 'Blocked'),
+    ('Triage', 'Closed'),
+    ('In Review', 'Open'),
+    ('In Review', 'Blocked'),
+    ('In Review', 'Closed'),
+    ('Blocked', 'Open'),
+    ('Blocked', 'Triage'),
+    ('Blocked', 'In Review'),
+    ('Closed', 'Reopened'),
+    ('Reopened', 'Triage'),
+    ('Reopened', 'In Review'),
+    ('Reopened', 'Blocked'),
+    ('Reopened', 'Closed');
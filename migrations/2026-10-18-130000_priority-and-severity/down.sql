@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+alter table tickets drop column severity;
+alter table tickets drop column priority;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- priority is stored as its rank (1 = Lowest, 5 = Highest) so tickets can be sorted by it
+alter table tickets add column priority integer not null default 3;
+alter table tickets add column severity text not null default 'Normal';
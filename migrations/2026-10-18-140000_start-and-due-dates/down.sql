@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+alter table tickets drop column due_date;
+alter table tickets drop column start_date;
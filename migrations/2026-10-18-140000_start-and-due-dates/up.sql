@@ -0,0 +1,3 @@
+-- Your SQL goes here
+alter table tickets add column start_date text;
+alter table tickets add column due_date text;
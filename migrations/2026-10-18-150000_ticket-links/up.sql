@@ -0,0 +1,7 @@
+-- Your SQL goes here
+create table ticket_links (
+    id integer primary key not null,
+    source_ticket_id integer not null references tickets(id),
+    target_ticket_id integer not null references tickets(id),
+    link_type text not null
+);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+drop index tickets_key;
+alter table tickets drop column key;
+alter table tickets drop column project_id;
+drop table projects;
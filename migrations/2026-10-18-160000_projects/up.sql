@@ -0,0 +1,17 @@
+-- Your SQL goes here
+create table projects (
+    id integer primary key not null,
+    key text not null unique,
+    name text not null,
+    -- number of the last ticket created in the project, numbers of deleted tickets are not reused
+    ticket_counter integer not null default 0
+);
+
+-- existing tickets move into a default project and keep their id as number
+insert into projects (id, key, name, ticket_counter)
+    select 1, 'CIRA', 'Cira', coalesce(max(id), 0) from tickets;
+
+alter table tickets add column project_id integer not null default 1 references projects(id);
+alter table tickets add column key text not null default '';
+update tickets set key = 'CIRA-' || id;
+create unique index tickets_key on tickets(key);
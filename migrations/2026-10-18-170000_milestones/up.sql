@@ -0,0 +1,10 @@
+-- Your SQL goes here
+create table milestones (
+    id integer primary key not null,
+    name text not null,
+    start_date text,
+    end_date text,
+    state text not null default 'Planned'
+);
+
+alter table tickets add column milestone_id integer references milestones(id);
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+-- the files themselves are stored on disk, named after the id of their attachment
+create table attachments (
+    id integer primary key not null,
+    ticket_id integer not null references tickets(id),
+    uploader_id integer not null references users(id),
+    file_name text not null,
+    mime_type text not null,
+    size integer not null,
+    checksum text not null,
+    created text not null
+);
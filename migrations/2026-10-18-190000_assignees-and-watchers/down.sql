@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+-- a ticket can only keep one of its assignees
+alter table tickets add column assigned_user integer;
+update tickets set assigned_user = (
+    select min(user_id) from ticket_assignees where ticket_assignees.ticket_id = tickets.id
+);
+
+drop table ticket_watchers;
+drop table ticket_assignees;
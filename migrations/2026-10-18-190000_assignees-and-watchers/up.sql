@@ -0,0 +1,17 @@
+-- Your SQL goes here
+create table ticket_assignees (
+    ticket_id integer not null references tickets(id),
+    user_id integer not null references users(id),
+    primary key (ticket_id, user_id)
+);
+
+create table ticket_watchers (
+    ticket_id integer not null references tickets(id),
+    user_id integer not null references users(id),
+    primary key (ticket_id, user_id)
+);
+
+insert into ticket_assignees (ticket_id, user_id)
+    select id, assigned_user from tickets where assigned_user is not null;
+
+alter table tickets drop column assigned_user;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+alter table tickets drop column deleted_by;
+alter table tickets drop column deleted_at;
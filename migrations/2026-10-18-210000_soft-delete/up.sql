@@ -0,0 +1,3 @@
+-- Your SQL goes here
+alter table tickets add column deleted_at text;
+alter table tickets add column deleted_by integer references users(id);
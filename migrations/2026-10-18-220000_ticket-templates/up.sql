@@ -0,0 +1,10 @@
+-- Your SQL goes here
+create table ticket_templates (
+    id integer primary key not null,
+    name text not null,
+    title_pattern text not null,
+    body text not null,
+    labels text not null,
+    status text references statuses(name),
+    assigned_user integer references users(id)
+);
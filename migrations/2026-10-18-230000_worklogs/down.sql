@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+drop table worklogs;
+
+alter table tickets drop column remaining_estimate;
+alter table tickets drop column original_estimate;
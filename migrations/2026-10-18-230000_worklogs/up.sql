@@ -0,0 +1,13 @@
+-- Your SQL goes here
+alter table tickets add column original_estimate integer;
+alter table tickets add column remaining_estimate integer;
+
+create table worklogs (
+    id integer primary key not null,
+    ticket_id integer not null references tickets(id),
+    user_id integer not null references users(id),
+    minutes integer not null,
+    date text not null,
+    note text,
+    created text not null
+);
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+alter table tickets add column story_points integer;
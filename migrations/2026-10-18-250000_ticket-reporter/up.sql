@@ -0,0 +1,10 @@
+-- Your SQL goes here
+alter table tickets add column reporter_id integer references users(id);
+
+-- whoever is recorded for the creation of a ticket filed it
+update tickets set reporter_id = (
+    select actor_id from ticket_history
+    where ticket_history.ticket_id = tickets.id
+    order by ticket_history.id
+    limit 1
+);
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+create table mentions (
+    id integer primary key not null,
+    ticket_id integer not null references tickets(id),
+    user_id integer not null references users(id),
+    actor_id integer not null references users(id),
+    created text not null
+);
+
+create index mentions_user_id on mentions(user_id);
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+drop index ticket_assignees_user_id;
+drop index tickets_deleted_at;
+drop index tickets_due_date;
+drop index tickets_reporter_id;
+drop index tickets_milestone_id;
+drop index tickets_status;
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+create index tickets_status on tickets(status);
+create index tickets_milestone_id on tickets(milestone_id);
+create index tickets_reporter_id on tickets(reporter_id);
+create index tickets_due_date on tickets(due_date);
+create index tickets_deleted_at on tickets(deleted_at);
+-- the primary key only covers lookups by ticket
+create index ticket_assignees_user_id on ticket_assignees(user_id);
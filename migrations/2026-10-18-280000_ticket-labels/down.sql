@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+alter table tickets add column labels text not null default '[]';
+
+update tickets set labels = (
+    select json_group_array(label_id) from (
+        select label_id from ticket_labels where ticket_labels.ticket_id = tickets.id order by label_id
+    )
+);
+
+drop table ticket_labels;
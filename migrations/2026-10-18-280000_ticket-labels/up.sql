@@ -0,0 +1,16 @@
+-- Your SQL goes here
+create table ticket_labels (
+    ticket_id integer not null references tickets(id),
+    label_id integer not null references labels(id),
+    primary key (ticket_id, label_id)
+);
+
+create index ticket_labels_label_id on ticket_labels(label_id);
+
+-- malformed arrays and ids of labels that don't exist anymore are dropped instead of failing the migration
+insert into ticket_labels (ticket_id, label_id)
+select distinct tickets.id, ticket_label.value
+from tickets, json_each(case when json_valid(tickets.labels) then tickets.labels else '[]' end) as ticket_label
+where ticket_label.value in (select id from labels);
+
+alter table tickets drop column labels;
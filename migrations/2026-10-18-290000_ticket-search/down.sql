@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+drop trigger tickets_search_update;
+drop trigger tickets_search_delete;
+drop trigger tickets_search_insert;
+drop table tickets_search;
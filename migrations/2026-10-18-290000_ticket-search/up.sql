@@ -0,0 +1,24 @@
+-- Your SQL goes here
+-- the index only stores the search terms, the text itself stays in tickets
+create virtual table tickets_search using fts5(
+    title,
+    body,
+    content = 'tickets',
+    content_rowid = 'id',
+    tokenize = 'porter unicode61'
+);
+
+insert into tickets_search (tickets_search) values ('rebuild');
+
+create trigger tickets_search_insert after insert on tickets begin
+    insert into tickets_search (rowid, title, body) values (new.id, new.title, new.body);
+end;
+
+create trigger tickets_search_delete after delete on tickets begin
+    insert into tickets_search (tickets_search, rowid, title, body) values ('delete', old.id, old.title, old.body);
+end;
+
+create trigger tickets_search_update after update of title, body on tickets begin
+    insert into tickets_search (tickets_search, rowid, title, body) values ('delete', old.id, old.title, old.body);
+    insert into tickets_search (rowid, title, body) values (new.id, new.title, new.body);
+end;
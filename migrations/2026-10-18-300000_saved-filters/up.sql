@@ -0,0 +1,12 @@
+-- Your SQL goes here
+create table saved_filters (
+    id integer primary key not null,
+    owner_id integer not null references users(id),
+    name text not null,
+    filter text not null,
+    shared boolean not null default false,
+    created text not null,
+    last_modified text not null
+);
+
+create index saved_filters_owner_id on saved_filters(owner_id);
@@ -0,0 +1,109 @@
+use actix_multipart::Multipart;
+use dotenvy::dotenv;
+use futures_util::StreamExt;
+use sha2::{Digest, Sha256};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+/// Used if MAX_ATTACHMENT_SIZE isn't set, 10 MiB.
+const DEFAULT_MAX_ATTACHMENT_SIZE: usize = 10 * 1024 * 1024;
+
+/// A file that was uploaded, but isn't stored yet.
+pub struct Upload {
+    pub file_name: String,
+    pub mime_type: String,
+    pub content: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub enum UploadError {
+    MissingFile,
+    TooLarge,
+    Invalid,
+}
+
+pub fn storage_directory() -> PathBuf {
+    dotenv().ok();
+
+    #[cfg(test)]
+    let variable_name = "TEST_ATTACHMENT_DIRECTORY";
+
+    #[cfg(not(test))]
+    let variable_name = "ATTACHMENT_DIRECTORY";
+
+    PathBuf::from(env::var(variable_name).unwrap_or_else(|_| "attachments".to_string()))
+}
+
+pub fn max_attachment_size() -> usize {
+    env::var("MAX_ATTACHMENT_SIZE")
+        .ok()
+        .and_then(|size| size.parse().ok())
+        .unwrap_or(DEFAULT_MAX_ATTACHMENT_SIZE)
+}
+
+/// Files are named after their attachment id, so uploaded file names never end up in a path.
+fn attachment_path(attachment_id: i32) -> PathBuf {
+    storage_directory().join(attachment_id.to_string())
+}
+
+pub fn checksum(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// Reads the field named "file" of a multipart upload.
+/// The file is kept in memory and rejected as soon as it exceeds `max_size`, so nothing too large is ever written.
+pub async fn read_upload(mut payload: Multipart, max_size: usize) -> Result<Upload, UploadError> {
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|_| UploadError::Invalid)?;
+
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let file_name = field
+            .content_disposition()
+            .and_then(|disposition| disposition.get_filename())
+            .unwrap_or("attachment")
+            .to_string();
+        let mime_type = field
+            .content_type()
+            .map(|mime| mime.to_string())
+            .unwrap_or_else(|| "application/octet-stream".to_string());
+        let mut content = Vec::new();
+
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|_| UploadError::Invalid)?;
+
+            if content.len() + chunk.len() > max_size {
+                return Err(UploadError::TooLarge);
+            }
+            content.extend_from_slice(&chunk);
+        }
+
+        return Ok(Upload {
+            file_name,
+            mime_type,
+            content,
+        });
+    }
+
+    Err(UploadError::MissingFile)
+}
+
+pub fn write_attachment_file(attachment_id: i32, content: &[u8]) -> io::Result<()> {
+    fs::create_dir_all(storage_directory())?;
+    fs::write(attachment_path(attachment_id), content)
+}
+
+pub fn read_attachment_file(attachment_id: i32) -> io::Result<Vec<u8>> {
+    fs::read(attachment_path(attachment_id))
+}
+
+/// A file that is already gone doesn't count as an error, the attachment is removed either way.
+pub fn remove_attachment_file(attachment_id: i32) -> io::Result<()> {
+    match fs::remove_file(attachment_path(attachment_id)) {
+        Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,104 @@
+use crate::dates::start_of_day_in_millis;
+use crate::models::{BurndownDay, HistoryEntry};
+use actix_web::cookie::time::Date;
+use std::collections::{BTreeMap, HashMap};
+
+/// Milliseconds since the epoch at which `day` ends in UTC, which is how history entries are timestamped.
+fn end_of_day_in_millis(day: Date) -> i128 {
+    start_of_day_in_millis(day) as i128 + 24 * 60 * 60 * 1000
+}
+
+/// The fields of a ticket as they were at some point, replayed from its history.
+#[derive(Default)]
+struct ReplayedTicket<'a> {
+    fields: HashMap<&'a str, Option<&'a str>>,
+    next_entry: usize,
+}
+
+impl<'a> ReplayedTicket<'a> {
+    fn replay_until(&mut self, entries: &[&'a HistoryEntry], until: i128) {
+        while let Some(entry) = entries.get(self.next_entry) {
+            if entry.changed.parse::<i128>().unwrap_or_default() >= until {
+                break;
+            }
+            self.fields
+                .insert(entry.field.as_str(), entry.new_value.as_deref());
+            self.next_entry += 1;
+        }
+    }
+
+    fn field(&self, name: &str) -> Option<&'a str> {
+        self.fields.get(name).copied().flatten()
+    }
+}
+
+/// Story points in scope, completed and remaining at the end of every day from `from` to `to`.
+/// Tickets are in scope while they exist outside the trash and, if a milestone is given, are planned for it.
+/// `history` has to be in chronological order.
+pub fn burndown_days(
+    history: &[HistoryEntry],
+    closed_statuses: &[String],
+    from: Date,
+    to: Date,
+    milestone_id: Option<i32>,
+) -> Vec<BurndownDay> {
+    let mut entries_by_ticket: BTreeMap<i32, Vec<&HistoryEntry>> = BTreeMap::new();
+    for entry in history {
+        entries_by_ticket
+            .entry(entry.ticket_id)
+            .or_default()
+            .push(entry);
+    }
+    let mut replayed_tickets: BTreeMap<i32, ReplayedTicket> = BTreeMap::new();
+    let milestone_id = milestone_id.map(|milestone_id| milestone_id.to_string());
+
+    let mut days = vec![];
+    let mut day = Some(from);
+    while let Some(current_day) = day.filter(|day| *day <= to) {
+        let end_of_day = end_of_day_in_millis(current_day);
+        let (mut total_points, mut completed_points) = (0, 0);
+
+        for (ticket_id, entries) in &entries_by_ticket {
+            let ticket = replayed_tickets.entry(*ticket_id).or_default();
+            ticket.replay_until(entries, end_of_day);
+
+            // every ticket has a status from its creation until it is purged
+            let Some(ticket_status) = ticket.field("status") else {
+                continue;
+            };
+            if ticket.field("deleted_at").is_some() {
+                continue;
+            }
+            if milestone_id.is_some() && ticket.field("milestone_id") != milestone_id.as_deref() {
+                continue;
+            }
+            let points = ticket
+                .field("story_points")
+                .and_then(|points| points.parse::<i64>().ok())
+                .unwrap_or_default();
+
+            total_points += points;
+            if closed_statuses.iter().any(|closed| closed == ticket_status) {
+                completed_points += points;
+            }
+        }
+
+        days.push(BurndownDay {
+            date: current_day.to_string(),
+            total_points,
+            completed_points,
+            remaining_points: total_points - completed_points,
+            ideal_points: 0.0,
+        });
+        day = current_day.next_day();
+    }
+
+    // the ideal line goes from the points in scope on the first day down to zero on the last day
+    let initial_points = days.first().map_or(0, |day| day.remaining_points) as f64;
+    let steps = days.len().saturating_sub(1).max(1) as f64;
+    for (index, day) in days.iter_mut().enumerate() {
+        day.ideal_points = initial_points * (1.0 - index as f64 / steps).max(0.0);
+    }
+
+    days
+}
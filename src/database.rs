@@ -1,109 +1,1926 @@
-use crate::filters::{
-    filter_by_assigned_user, filter_by_labels, filter_by_status, filter_by_title,
-};
+use crate::attachments::{checksum, Upload};
+use crate::burndown::burndown_days;
+use crate::dates::{is_valid_date, today};
+use crate::filters::filter_tickets_query;
+use crate::mentions::mentioned_users;
+use crate::merge_patch::apply_merge_patch;
 use crate::models::{
-    DataBaseUser, DatabaseSession, DisplayUser, NewSession, NewTicket, NewUser, SqliteTicket,
-    Status, Ticket,
+    Attachment, Burndown, Comment, DataBaseUser, DatabaseSession, DisplayUser, HistoryEntry, Label,
+    LinkType, Mention, Milestone, MilestoneSummary, NewAttachment, NewComment, NewHistoryEntry,
+    NewLabel, NewMention, NewMilestone, NewProject, NewSavedFilter, NewSession, NewStatus,
+    NewStatusTransition, NewTicket, NewTicketLink, NewTicketTemplate, NewUser, NewWorklog, Project,
+    SavedFilter, SearchHit, SearchResult, SqliteSavedFilter, SqliteTicket, SqliteTicketTemplate,
+    Status, StatusTransition, Ticket, TicketLink, TicketTemplate, TicketWorklogTotal,
+    UserWorklogTotal, Worklog, WorklogSummary,
+};
+use crate::payloads::{
+    BulkOperations, BurndownQuery, CommentPayload, FilterPayload, LinkPayload, MilestonePayload,
+    SavedFilterPayload, TemplatePayload, TicketPayload, WorklogPayload, WorklogQuery,
 };
-use crate::payloads::{FilterPayload, TicketPayload};
+use crate::schema::attachments;
+use crate::schema::labels::dsl::labels as all_labels;
+use crate::schema::labels::{id as label_id, name as label_name};
+use crate::schema::mentions;
+use crate::schema::milestones;
+use crate::schema::projects;
+use crate::schema::saved_filters;
 use crate::schema::sessions::dsl::sessions;
 use crate::schema::sessions::token;
+use crate::schema::status_transitions;
+use crate::schema::statuses::dsl::statuses as all_statuses;
+use crate::schema::statuses::{id as status_id, is_closed, name as status_name};
+use crate::schema::ticket_assignees;
+use crate::schema::ticket_comments;
+use crate::schema::ticket_history;
+use crate::schema::ticket_labels;
+use crate::schema::ticket_links;
+use crate::schema::ticket_templates;
+use crate::schema::ticket_watchers;
 use crate::schema::tickets::dsl::tickets;
-use crate::schema::tickets::{body, id, labels, last_modified, status, title};
+use crate::schema::tickets::{
+    body, deleted_at, deleted_by, due_date, id, key, last_modified, milestone_id,
+    original_estimate, priority, project_id, remaining_estimate, severity, start_date, status,
+    story_points, title, version,
+};
 use crate::schema::users::dsl::users;
 use crate::schema::users::{display_name, email, id as user_id};
+use crate::schema::worklogs;
+use actix_web::cookie::time::Date;
 use actix_web::web::Json;
 use argonautica::Hasher;
-use diesel::{Connection, ExpressionMethods, QueryDsl, QueryResult, RunQueryDsl, SqliteConnection};
+use diesel::dsl::{Filter, IsNotNull, Select};
+use diesel::result::Error;
+use diesel::sql_types::{BigInt, Text};
+use diesel::{
+    BoolExpressionMethods, Connection, ExpressionMethods, QueryDsl, QueryResult, RunQueryDsl,
+    SqliteConnection,
+};
 use dotenvy::dotenv;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-pub struct DataBase {
-    pub connection: SqliteConnection,
+const DEFAULT_TRASH_RETENTION_DAYS: u64 = 30;
+
+pub struct DataBase {
+    pub connection: SqliteConnection,
+}
+
+impl DataBase {
+    pub fn new() -> Self {
+        dotenv().ok();
+
+        #[cfg(test)]
+        let variable_name = "TEST_DATABASE_URL";
+
+        #[cfg(not(test))]
+        let variable_name = "DATABASE_URL";
+
+        let database_url = env::var(variable_name).expect("Could not find database url in .env");
+
+        DataBase {
+            connection: SqliteConnection::establish(&database_url)
+                .unwrap_or_else(|_| panic!("Error connecting to {}", database_url)),
+        }
+    }
+}
+
+/// Errors of operations that validate a ticket before writing it.
+#[derive(Debug)]
+pub enum TicketError {
+    Database(Error),
+    UnknownLabel(i32),
+    UnknownStatus(String),
+    InvalidTransition { from: String, to: String },
+    InvalidDate(String),
+    StartAfterDue,
+    UnknownMilestone(i32),
+    UnknownUser(i32),
+    InvalidPatch(String),
+    VersionMismatch,
+    UnknownTemplate(i32),
+    InvalidTicket(String),
+    NegativeEstimate(i32),
+}
+
+impl From<Error> for TicketError {
+    fn from(err: Error) -> Self {
+        TicketError::Database(err)
+    }
+}
+
+/// Errors of changing several tickets at once.
+#[derive(Debug)]
+pub enum BulkError {
+    Database,
+    /// At least one ticket could not be changed, so none of them were.
+    Failed(Vec<(i32, Result<Ticket, TicketError>)>),
+}
+
+impl From<Error> for BulkError {
+    fn from(_: Error) -> Self {
+        BulkError::Database
+    }
+}
+
+/// Errors of linking two tickets.
+#[derive(Debug)]
+pub enum LinkError {
+    Database(Error),
+    UnknownTicket(i32),
+    SelfLink,
+    AlreadyLinked,
+    AlreadyHasParent,
+    Cycle,
+}
+
+impl From<Error> for LinkError {
+    fn from(err: Error) -> Self {
+        LinkError::Database(err)
+    }
+}
+
+fn current_time_in_millis() -> String {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::new(0, 0))
+        .as_millis()
+        .to_string()
+}
+
+/// Stores a mention for every user that is mentioned in `after` but wasn't in `before`,
+/// so editing a ticket doesn't notify everybody who was mentioned before again.
+fn record_mentions(
+    connection: &mut SqliteConnection,
+    before: Option<&SqliteTicket>,
+    after: &SqliteTicket,
+    actor_id: i32,
+) -> QueryResult<usize> {
+    let known_users = users
+        .select((user_id, display_name))
+        .load::<(i32, String)>(connection)?;
+    let mentioned_in = |ticket: &SqliteTicket| {
+        let mut mentioned = mentioned_users(&ticket.title, &known_users);
+        mentioned.extend(mentioned_users(&ticket.body, &known_users));
+        mentioned
+    };
+    let already_mentioned = before.map(mentioned_in).unwrap_or_default();
+    let created = current_time_in_millis();
+
+    let new_mentions: Vec<NewMention> = mentioned_in(after)
+        .difference(&already_mentioned)
+        // nobody has to be notified about their own writing
+        .filter(|mentioned_user| **mentioned_user != actor_id)
+        .map(|mentioned_user| NewMention {
+            ticket_id: after.id,
+            user_id: *mentioned_user,
+            actor_id,
+            created: created.clone(),
+        })
+        .collect();
+
+    diesel::insert_into(mentions::table)
+        .values(&new_mentions)
+        .execute(connection)
+}
+
+pub fn get_user_mentions(
+    connection: &mut SqliteConnection,
+    mentioned_user_id: i32,
+) -> QueryResult<Vec<Mention>> {
+    mentions::table
+        .inner_join(tickets)
+        .filter(mentions::user_id.eq(mentioned_user_id))
+        .filter(deleted_at.is_null())
+        .select(mentions::all_columns)
+        .order(mentions::id.desc())
+        .load::<Mention>(connection)
+}
+
+/// Writes one history entry per tracked field that differs between `before` and `after`.
+/// A ticket that is created has no `before`, a ticket that is deleted has no `after`.
+fn record_history(
+    connection: &mut SqliteConnection,
+    before: Option<&SqliteTicket>,
+    after: Option<&SqliteTicket>,
+    actor_id: i32,
+) -> QueryResult<usize> {
+    let (ticket_id, tracked_fields) = match after.or(before) {
+        Some(ticket) => (ticket.id, ticket.tracked_fields()),
+        None => return Ok(0),
+    };
+    let old_fields = before.map(SqliteTicket::tracked_fields);
+    let new_fields = after.map(SqliteTicket::tracked_fields);
+    let value_at = |fields: &Option<Vec<(&str, Option<String>)>>, index: usize| {
+        fields.as_ref().and_then(|fields| fields[index].1.clone())
+    };
+    let changed = current_time_in_millis();
+
+    let entries: Vec<NewHistoryEntry> = tracked_fields
+        .iter()
+        .enumerate()
+        .map(|(index, (field, _))| NewHistoryEntry {
+            ticket_id,
+            actor_id,
+            field: field.to_string(),
+            old_value: value_at(&old_fields, index),
+            new_value: value_at(&new_fields, index),
+            changed: changed.clone(),
+        })
+        .filter(|entry| entry.old_value != entry.new_value)
+        .collect();
+
+    diesel::insert_into(ticket_history::table)
+        .values(&entries)
+        .execute(connection)
+}
+
+/// Assignees live in their own table, so changes to them are recorded on their own.
+/// Labels are recorded as a json array, `None` before the ticket was created and after it was purged.
+fn record_label_history(
+    connection: &mut SqliteConnection,
+    ticket_id: i32,
+    old_labels: Option<&[i32]>,
+    new_labels: Option<&[i32]>,
+    actor_id: i32,
+) -> QueryResult<usize> {
+    let as_value = |label_ids: Option<&[i32]>| {
+        label_ids.map(|label_ids| serde_json::to_string(label_ids).unwrap())
+    };
+    let entry = NewHistoryEntry {
+        ticket_id,
+        actor_id,
+        field: "labels".to_string(),
+        old_value: as_value(old_labels),
+        new_value: as_value(new_labels),
+        changed: current_time_in_millis(),
+    };
+
+    if entry.old_value == entry.new_value {
+        return Ok(0);
+    }
+
+    diesel::insert_into(ticket_history::table)
+        .values(&entry)
+        .execute(connection)
+}
+
+fn record_assignee_history(
+    connection: &mut SqliteConnection,
+    ticket_id: i32,
+    old_assignees: &[i32],
+    new_assignees: &[i32],
+    actor_id: i32,
+) -> QueryResult<usize> {
+    let as_value = |assignees: &[i32]| {
+        (!assignees.is_empty()).then(|| serde_json::to_string(assignees).unwrap())
+    };
+    let entry = NewHistoryEntry {
+        ticket_id,
+        actor_id,
+        field: "assignees".to_string(),
+        old_value: as_value(old_assignees),
+        new_value: as_value(new_assignees),
+        changed: current_time_in_millis(),
+    };
+
+    if entry.old_value == entry.new_value {
+        return Ok(0);
+    }
+
+    diesel::insert_into(ticket_history::table)
+        .values(&entry)
+        .execute(connection)
+}
+
+fn validate_users(connection: &mut SqliteConnection, user_ids: &[i32]) -> Result<(), TicketError> {
+    let existing_users: Vec<i32> = users
+        .filter(user_id.eq_any(user_ids))
+        .select(user_id)
+        .load(connection)?;
+
+    match user_ids.iter().find(|user| !existing_users.contains(user)) {
+        Some(unknown_user) => Err(TicketError::UnknownUser(*unknown_user)),
+        None => Ok(()),
+    }
+}
+
+/// Sorted and without duplicates, so the same set of users or labels is always stored and compared the same way.
+fn unique_ids(ids: &[i32]) -> Vec<i32> {
+    let mut ids = ids.to_vec();
+    ids.sort_unstable();
+    ids.dedup();
+    ids
+}
+
+fn replace_labels(
+    connection: &mut SqliteConnection,
+    ticket_id: i32,
+    label_ids: &[i32],
+) -> QueryResult<usize> {
+    diesel::delete(ticket_labels::table.filter(ticket_labels::ticket_id.eq(ticket_id)))
+        .execute(connection)?;
+
+    let rows: Vec<_> = label_ids
+        .iter()
+        .map(|label| {
+            (
+                ticket_labels::ticket_id.eq(ticket_id),
+                ticket_labels::label_id.eq(label),
+            )
+        })
+        .collect();
+    diesel::insert_into(ticket_labels::table)
+        .values(&rows)
+        .execute(connection)
+}
+
+fn replace_assignees(
+    connection: &mut SqliteConnection,
+    ticket_id: i32,
+    assignees: &[i32],
+) -> QueryResult<usize> {
+    diesel::delete(ticket_assignees::table.filter(ticket_assignees::ticket_id.eq(ticket_id)))
+        .execute(connection)?;
+
+    let rows: Vec<_> = assignees
+        .iter()
+        .map(|assignee| {
+            (
+                ticket_assignees::ticket_id.eq(ticket_id),
+                ticket_assignees::user_id.eq(assignee),
+            )
+        })
+        .collect();
+    diesel::insert_into(ticket_assignees::table)
+        .values(&rows)
+        .execute(connection)
+}
+
+fn replace_watchers(
+    connection: &mut SqliteConnection,
+    ticket_id: i32,
+    watchers: &[i32],
+) -> QueryResult<usize> {
+    diesel::delete(ticket_watchers::table.filter(ticket_watchers::ticket_id.eq(ticket_id)))
+        .execute(connection)?;
+
+    let rows: Vec<_> = watchers
+        .iter()
+        .map(|watcher| {
+            (
+                ticket_watchers::ticket_id.eq(ticket_id),
+                ticket_watchers::user_id.eq(watcher),
+            )
+        })
+        .collect();
+    diesel::insert_into(ticket_watchers::table)
+        .values(&rows)
+        .execute(connection)
+}
+
+/// Turns tickets as they are stored into tickets as they are sent, together with their labels, assignees and watchers.
+pub fn hydrate_tickets(
+    connection: &mut SqliteConnection,
+    sqlite_tickets: &[SqliteTicket],
+) -> QueryResult<Vec<Ticket>> {
+    let ticket_ids: Vec<i32> = sqlite_tickets.iter().map(|ticket| ticket.id).collect();
+    let mut labels_by_ticket: HashMap<i32, Vec<i32>> = HashMap::new();
+    let mut assignees_by_ticket: HashMap<i32, Vec<i32>> = HashMap::new();
+    let mut watchers_by_ticket: HashMap<i32, Vec<i32>> = HashMap::new();
+
+    for (ticket_id, label) in ticket_labels::table
+        .filter(ticket_labels::ticket_id.eq_any(&ticket_ids))
+        .order((ticket_labels::ticket_id, ticket_labels::label_id))
+        .load::<(i32, i32)>(connection)?
+    {
+        labels_by_ticket.entry(ticket_id).or_default().push(label);
+    }
+
+    for (ticket_id, assignee) in ticket_assignees::table
+        .filter(ticket_assignees::ticket_id.eq_any(&ticket_ids))
+        .order((ticket_assignees::ticket_id, ticket_assignees::user_id))
+        .load::<(i32, i32)>(connection)?
+    {
+        assignees_by_ticket
+            .entry(ticket_id)
+            .or_default()
+            .push(assignee);
+    }
+
+    for (ticket_id, watcher) in ticket_watchers::table
+        .filter(ticket_watchers::ticket_id.eq_any(&ticket_ids))
+        .order((ticket_watchers::ticket_id, ticket_watchers::user_id))
+        .load::<(i32, i32)>(connection)?
+    {
+        watchers_by_ticket
+            .entry(ticket_id)
+            .or_default()
+            .push(watcher);
+    }
+
+    Ok(sqlite_tickets
+        .iter()
+        .map(|ticket| {
+            ticket.to_ticket(
+                labels_by_ticket.remove(&ticket.id).unwrap_or_default(),
+                assignees_by_ticket.remove(&ticket.id).unwrap_or_default(),
+                watchers_by_ticket.remove(&ticket.id).unwrap_or_default(),
+            )
+        })
+        .collect())
+}
+
+pub fn hydrate_ticket(
+    connection: &mut SqliteConnection,
+    sqlite_ticket: &SqliteTicket,
+) -> QueryResult<Ticket> {
+    let mut hydrated = hydrate_tickets(connection, std::slice::from_ref(sqlite_ticket))?;
+
+    Ok(hydrated.remove(0))
+}
+
+fn validate_labels(
+    connection: &mut SqliteConnection,
+    ticket_labels: &[i32],
+) -> Result<(), TicketError> {
+    let existing_labels: Vec<i32> = all_labels
+        .filter(label_id.eq_any(ticket_labels))
+        .select(label_id)
+        .load(connection)?;
+
+    match ticket_labels
+        .iter()
+        .find(|ticket_label| !existing_labels.contains(ticket_label))
+    {
+        Some(unknown_label) => Err(TicketError::UnknownLabel(*unknown_label)),
+        None => Ok(()),
+    }
+}
+
+fn known_status(
+    connection: &mut SqliteConnection,
+    ticket_status: &str,
+) -> Result<Status, TicketError> {
+    match get_status_by_name(ticket_status, connection) {
+        Ok(known_status) => Ok(known_status),
+        Err(Error::NotFound) => Err(TicketError::UnknownStatus(ticket_status.to_string())),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Status a new ticket starts in.
+/// Makes no sense to create a closed ticket, so a closed status is replaced by the first open one.
+fn initial_status(
+    connection: &mut SqliteConnection,
+    requested_status: &str,
+) -> Result<String, TicketError> {
+    let requested_status = known_status(connection, requested_status)?;
+
+    if !requested_status.is_closed {
+        return Ok(requested_status.name);
+    }
+
+    Ok(all_statuses
+        .filter(is_closed.eq(false))
+        .order(status_id)
+        .select(status_name)
+        .first(connection)?)
+}
+
+fn validate_transition(
+    connection: &mut SqliteConnection,
+    from: &str,
+    to: &str,
+) -> Result<(), TicketError> {
+    known_status(connection, to)?;
+
+    if from == to {
+        return Ok(());
+    }
+
+    match get_transition(from, to, connection) {
+        Ok(_) => Ok(()),
+        Err(Error::NotFound) => Err(TicketError::InvalidTransition {
+            from: from.to_string(),
+            to: to.to_string(),
+        }),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// `expected_versions` are the versions a client sent in `If-Match`, `None` if any version will do.
+fn validate_version(
+    ticket: &SqliteTicket,
+    expected_versions: Option<&[i32]>,
+) -> Result<(), TicketError> {
+    match expected_versions {
+        Some(expected_versions) if !expected_versions.contains(&ticket.version) => {
+            Err(TicketError::VersionMismatch)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn increment_version(connection: &mut SqliteConnection, ticket_id: i32) -> QueryResult<usize> {
+    diesel::update(tickets.filter(id.eq(ticket_id)))
+        .set(version.eq(version + 1))
+        .execute(connection)
+}
+
+fn validate_milestone(
+    connection: &mut SqliteConnection,
+    ticket_milestone_id: Option<i32>,
+) -> Result<(), TicketError> {
+    match ticket_milestone_id {
+        Some(ticket_milestone_id) => match get_single_milestone(ticket_milestone_id, connection) {
+            Ok(_) => Ok(()),
+            Err(Error::NotFound) => Err(TicketError::UnknownMilestone(ticket_milestone_id)),
+            Err(err) => Err(err.into()),
+        },
+        None => Ok(()),
+    }
+}
+
+fn validate_estimates(ticket: &TicketPayload) -> Result<(), TicketError> {
+    match [
+        ticket.original_estimate,
+        ticket.remaining_estimate,
+        ticket.story_points,
+    ]
+    .into_iter()
+    .flatten()
+    .find(|minutes| *minutes < 0)
+    {
+        Some(minutes) => Err(TicketError::NegativeEstimate(minutes)),
+        None => Ok(()),
+    }
+}
+
+fn validate_dates(ticket: &TicketPayload) -> Result<(), TicketError> {
+    for date in [&ticket.start_date, &ticket.due_date].into_iter().flatten() {
+        if !is_valid_date(date) {
+            return Err(TicketError::InvalidDate(date.clone()));
+        }
+    }
+
+    match (&ticket.start_date, &ticket.due_date) {
+        (Some(start), Some(due)) if start > due => Err(TicketError::StartAfterDue),
+        _ => Ok(()),
+    }
+}
+
+pub fn create_ticket(
+    connection: &mut SqliteConnection,
+    new_ticket: Json<TicketPayload>,
+    ticket_project_id: i32,
+    actor_id: i32,
+) -> Result<Ticket, TicketError> {
+    use crate::schema::tickets;
+
+    let label_ids = unique_ids(&new_ticket.labels);
+    let assignees = unique_ids(&new_ticket.assignee_ids().unwrap_or_default());
+    let watchers = unique_ids(new_ticket.watchers.as_deref().unwrap_or_default());
+
+    validate_labels(connection, &label_ids)?;
+    validate_users(connection, &assignees)?;
+    validate_users(connection, &watchers)?;
+    validate_dates(&new_ticket)?;
+    validate_estimates(&new_ticket)?;
+    validate_milestone(connection, new_ticket.milestone_id)?;
+    let ticket_status = initial_status(connection, &new_ticket.status)?;
+
+    connection.transaction(|connection| {
+        // the counter is bumped inside the transaction so concurrent tickets can't get the same number
+        let project: Project = diesel::update(projects::table.find(ticket_project_id))
+            .set(projects::ticket_counter.eq(projects::ticket_counter + 1))
+            .get_result(connection)?;
+
+        let now_in_millis = current_time_in_millis();
+        let new_ticket = NewTicket {
+            title: new_ticket.title.clone(),
+            body: new_ticket.body.clone(),
+            created: now_in_millis.clone(),
+            last_modified: now_in_millis,
+            // status is required to be sent by user to not have ugly null handling in update function
+            status: ticket_status,
+            priority: new_ticket.priority.rank(),
+            severity: new_ticket.severity.to_string(),
+            start_date: new_ticket.start_date.clone(),
+            due_date: new_ticket.due_date.clone(),
+            project_id: project.id,
+            key: project.ticket_key(project.ticket_counter),
+            milestone_id: new_ticket.milestone_id,
+            original_estimate: new_ticket.original_estimate,
+            remaining_estimate: new_ticket.remaining_estimate,
+            story_points: new_ticket.story_points,
+            reporter_id: Some(actor_id),
+        };
+
+        let created_ticket: SqliteTicket = diesel::insert_into(tickets::table)
+            .values(&new_ticket)
+            .get_result(connection)?;
+        replace_labels(connection, created_ticket.id, &label_ids)?;
+        replace_assignees(connection, created_ticket.id, &assignees)?;
+        replace_watchers(connection, created_ticket.id, &watchers)?;
+        record_history(connection, None, Some(&created_ticket), actor_id)?;
+        record_label_history(
+            connection,
+            created_ticket.id,
+            None,
+            Some(&label_ids),
+            actor_id,
+        )?;
+        record_mentions(connection, None, &created_ticket, actor_id)?;
+        record_assignee_history(connection, created_ticket.id, &[], &assignees, actor_id)?;
+
+        Ok(hydrate_ticket(connection, &created_ticket)?)
+    })
+}
+
+pub fn get_all_tickets(connection: &mut SqliteConnection) -> QueryResult<Vec<Ticket>> {
+    let all_tickets = tickets
+        .filter(deleted_at.is_null())
+        .load::<SqliteTicket>(connection)?;
+
+    hydrate_tickets(connection, &all_tickets)
+}
+
+pub fn get_single_ticket(
+    ticket_id: i32,
+    connection: &mut SqliteConnection,
+) -> QueryResult<SqliteTicket> {
+    tickets
+        .filter(id.eq(ticket_id))
+        .filter(deleted_at.is_null())
+        .get_result(connection)
+}
+
+pub fn get_ticket_by_key(
+    ticket_key: &str,
+    connection: &mut SqliteConnection,
+) -> QueryResult<SqliteTicket> {
+    tickets
+        .filter(key.eq(ticket_key))
+        .filter(deleted_at.is_null())
+        .get_result(connection)
+}
+
+pub fn get_project_tickets(
+    ticket_project_id: i32,
+    connection: &mut SqliteConnection,
+) -> QueryResult<Vec<Ticket>> {
+    let project_tickets = tickets
+        .filter(project_id.eq(ticket_project_id))
+        .filter(deleted_at.is_null())
+        .load::<SqliteTicket>(connection)?;
+
+    hydrate_tickets(connection, &project_tickets)
+}
+
+/// Moves a ticket to the trash, from where it can be restored until it is purged.
+pub fn delete_ticket(
+    connection: &mut SqliteConnection,
+    ticket_id: i32,
+    actor_id: i32,
+    expected_versions: Option<&[i32]>,
+) -> Result<Ticket, TicketError> {
+    connection.transaction(|connection| {
+        let ticket = get_single_ticket(ticket_id, connection)?;
+        validate_version(&ticket, expected_versions)?;
+
+        let trashed_ticket: SqliteTicket = diesel::update(tickets.filter(id.eq(ticket_id)))
+            .set((
+                deleted_at.eq(current_time_in_millis()),
+                deleted_by.eq(actor_id),
+                version.eq(version + 1),
+            ))
+            .get_result(connection)?;
+        record_history(connection, Some(&ticket), Some(&trashed_ticket), actor_id)?;
+
+        Ok(hydrate_ticket(connection, &trashed_ticket)?)
+    })
+}
+
+pub fn get_trashed_tickets(connection: &mut SqliteConnection) -> QueryResult<Vec<Ticket>> {
+    let trashed_tickets = tickets
+        .filter(deleted_at.is_not_null())
+        .order(deleted_at)
+        .load::<SqliteTicket>(connection)?;
+
+    hydrate_tickets(connection, &trashed_tickets)
+}
+
+pub fn get_trashed_ticket(
+    ticket_id: i32,
+    connection: &mut SqliteConnection,
+) -> QueryResult<SqliteTicket> {
+    tickets
+        .filter(id.eq(ticket_id))
+        .filter(deleted_at.is_not_null())
+        .get_result(connection)
+}
+
+/// How long tickets stay in the trash, `TRASH_RETENTION_DAYS` in the .env file or 30 days.
+pub fn trash_retention() -> Duration {
+    let days = env::var("TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|days| days.parse().ok())
+        .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS);
+
+    Duration::from_secs(days * 24 * 60 * 60)
+}
+
+/// Tickets that have been in the trash for longer than `retention`.
+pub fn get_expired_tickets(
+    connection: &mut SqliteConnection,
+    retention: Duration,
+) -> QueryResult<Vec<SqliteTicket>> {
+    let now: u128 = current_time_in_millis().parse().unwrap_or_default();
+    let expired_before = now.saturating_sub(retention.as_millis());
+
+    Ok(tickets
+        .filter(deleted_at.is_not_null())
+        .load::<SqliteTicket>(connection)?
+        .into_iter()
+        .filter(|ticket| {
+            ticket
+                .deleted_at
+                .as_deref()
+                .and_then(|trashed| trashed.parse::<u128>().ok())
+                .is_some_and(|trashed| trashed < expired_before)
+        })
+        .collect())
+}
+
+pub fn restore_ticket(
+    connection: &mut SqliteConnection,
+    ticket_id: i32,
+    actor_id: i32,
+) -> QueryResult<Ticket> {
+    connection.transaction(|connection| {
+        let trashed_ticket = get_trashed_ticket(ticket_id, connection)?;
+
+        let restored_ticket: SqliteTicket = diesel::update(tickets.filter(id.eq(ticket_id)))
+            .set((
+                deleted_at.eq(None::<String>),
+                deleted_by.eq(None::<i32>),
+                version.eq(version + 1),
+            ))
+            .get_result(connection)?;
+        record_history(
+            connection,
+            Some(&trashed_ticket),
+            Some(&restored_ticket),
+            actor_id,
+        )?;
+
+        hydrate_ticket(connection, &restored_ticket)
+    })
+}
+
+/// Permanently deletes a ticket from the trash, together with everything that belongs to it.
+pub fn purge_ticket(
+    connection: &mut SqliteConnection,
+    ticket_id: i32,
+    actor_id: i32,
+) -> QueryResult<Ticket> {
+    connection.transaction(|connection| {
+        let sqlite_ticket = get_trashed_ticket(ticket_id, connection)?;
+        let ticket = hydrate_ticket(connection, &sqlite_ticket)?;
+
+        diesel::delete(ticket_comments::table.filter(ticket_comments::ticket_id.eq(ticket_id)))
+            .execute(connection)?;
+        diesel::delete(worklogs::table.filter(worklogs::ticket_id.eq(ticket_id)))
+            .execute(connection)?;
+        diesel::delete(mentions::table.filter(mentions::ticket_id.eq(ticket_id)))
+            .execute(connection)?;
+        replace_labels(connection, ticket_id, &[])?;
+        replace_assignees(connection, ticket_id, &[])?;
+        replace_watchers(connection, ticket_id, &[])?;
+        diesel::delete(attachments::table.filter(attachments::ticket_id.eq(ticket_id)))
+            .execute(connection)?;
+        diesel::delete(
+            ticket_links::table.filter(
+                ticket_links::source_ticket_id
+                    .eq(ticket_id)
+                    .or(ticket_links::target_ticket_id.eq(ticket_id)),
+            ),
+        )
+        .execute(connection)?;
+
+        let purged_ticket: SqliteTicket =
+            diesel::delete(tickets.filter(id.eq(ticket_id))).get_result(connection)?;
+        record_history(connection, Some(&purged_ticket), None, actor_id)?;
+        record_label_history(connection, ticket_id, Some(&ticket.labels), None, actor_id)?;
+        record_assignee_history(connection, ticket_id, &ticket.assignees, &[], actor_id)?;
+
+        Ok(ticket)
+    })
+}
+
+pub fn edit_ticket(
+    connection: &mut SqliteConnection,
+    ticket: Json<TicketPayload>,
+    ticket_id: i32,
+    actor_id: i32,
+    expected_versions: Option<&[i32]>,
+) -> Result<Ticket, TicketError> {
+    let label_ids = unique_ids(&ticket.labels);
+    let assignees = ticket.assignee_ids().as_deref().map(unique_ids);
+    let watchers = ticket.watchers.as_deref().map(unique_ids);
+
+    connection.transaction(|connection| {
+        let old_ticket = get_single_ticket(ticket_id, connection)?;
+        validate_version(&old_ticket, expected_versions)?;
+        let old_hydrated_ticket = hydrate_ticket(connection, &old_ticket)?;
+        validate_labels(connection, &label_ids)?;
+        validate_users(connection, assignees.as_deref().unwrap_or_default())?;
+        validate_users(connection, watchers.as_deref().unwrap_or_default())?;
+        validate_dates(&ticket)?;
+        validate_estimates(&ticket)?;
+        validate_milestone(connection, ticket.milestone_id)?;
+        validate_transition(connection, &old_ticket.status, &ticket.status)?;
+
+        let updated_ticket: SqliteTicket = diesel::update(tickets.filter(id.eq(ticket_id)))
+            .set((
+                title.eq(&ticket.title),
+                body.eq(&ticket.body),
+                last_modified.eq(current_time_in_millis()),
+                status.eq(&ticket.status),
+                priority.eq(ticket.priority.rank()),
+                severity.eq(ticket.severity.to_string()),
+                start_date.eq(&ticket.start_date),
+                due_date.eq(&ticket.due_date),
+                milestone_id.eq(ticket.milestone_id),
+                original_estimate.eq(ticket.original_estimate),
+                remaining_estimate.eq(ticket.remaining_estimate),
+                story_points.eq(ticket.story_points),
+                version.eq(version + 1),
+            ))
+            .get_result(connection)?;
+        record_history(
+            connection,
+            Some(&old_ticket),
+            Some(&updated_ticket),
+            actor_id,
+        )?;
+        record_mentions(connection, Some(&old_ticket), &updated_ticket, actor_id)?;
+        replace_labels(connection, ticket_id, &label_ids)?;
+        record_label_history(
+            connection,
+            ticket_id,
+            Some(&old_hydrated_ticket.labels),
+            Some(&label_ids),
+            actor_id,
+        )?;
+        if let Some(assignees) = &assignees {
+            replace_assignees(connection, ticket_id, assignees)?;
+            record_assignee_history(
+                connection,
+                ticket_id,
+                &old_hydrated_ticket.assignees,
+                assignees,
+                actor_id,
+            )?;
+        }
+        if let Some(watchers) = &watchers {
+            replace_watchers(connection, ticket_id, watchers)?;
+        }
+
+        Ok(hydrate_ticket(connection, &updated_ticket)?)
+    })
+}
+
+/// Applies `operations` to every ticket, either all tickets are changed or none of them.
+pub fn bulk_edit_tickets(
+    connection: &mut SqliteConnection,
+    ticket_ids: &[i32],
+    operations: &BulkOperations,
+    actor_id: i32,
+) -> Result<Vec<Ticket>, BulkError> {
+    connection.transaction(|connection| {
+        let results = ticket_ids
+            .iter()
+            .map(|&ticket_id| {
+                let result = get_single_ticket(ticket_id, connection)
+                    .and_then(|ticket| hydrate_ticket(connection, &ticket))
+                    .map_err(TicketError::from)
+                    .and_then(|ticket| {
+                        let mut payload = TicketPayload::from_ticket(&ticket);
+                        operations.apply(&mut payload);
+
+                        edit_ticket(connection, Json(payload), ticket_id, actor_id, None)
+                    });
+
+                (ticket_id, result)
+            })
+            .collect::<Vec<_>>();
+
+        if results.iter().any(|(_, result)| result.is_err()) {
+            return Err(BulkError::Failed(results));
+        }
+
+        Ok(results
+            .into_iter()
+            .filter_map(|(_, result)| result.ok())
+            .collect())
+    })
+}
+
+/// Replaces `assigned_user` with the equivalent `assignees`, unless those are given as well.
+/// That way it overrides the assignees of the document the ticket is merged into.
+fn assigned_user_as_assignees(ticket: &mut Value) {
+    if let Value::Object(fields) = ticket {
+        if let Some(assigned_user) = fields.remove("assigned_user") {
+            if !fields.contains_key("assignees") {
+                let assignees = match assigned_user {
+                    Value::Null => Value::Array(vec![]),
+                    assigned_user => Value::Array(vec![assigned_user]),
+                };
+                fields.insert("assignees".to_string(), assignees);
+            }
+        }
+    }
+}
+
+/// Replaces `null` for `labels`, `assignees` and `watchers` with an empty list. A merge patch would
+/// remove them otherwise, which either fails or leaves them as they are instead of emptying them.
+fn null_lists_as_empty(patch: &mut Value) {
+    if let Value::Object(fields) = patch {
+        for field in ["labels", "assignees", "watchers"] {
+            if let Some(list @ Value::Null) = fields.get_mut(field) {
+                *list = Value::Array(vec![]);
+            }
+        }
+    }
+}
+
+/// Priority and severity can't be cleared, and once removed by a merge patch they would silently
+/// fall back to their defaults. Returns the first of them the patch sets to `null`.
+fn null_without_default(patch: &Value) -> Option<&'static str> {
+    ["priority", "severity"]
+        .into_iter()
+        .find(|field| patch.get(field).is_some_and(Value::is_null))
+}
+
+/// Turns a submitted ticket into a `TicketPayload`. If it names a `template_id`,
+/// the defaults of that template are used for everything the ticket leaves out.
+pub fn apply_ticket_template(
+    connection: &mut SqliteConnection,
+    mut submitted: Value,
+) -> Result<TicketPayload, TicketError> {
+    let template_id = match submitted
+        .as_object_mut()
+        .and_then(|ticket| ticket.remove("template_id"))
+    {
+        None | Some(Value::Null) => None,
+        Some(template_id) => Some(
+            template_id
+                .as_i64()
+                .and_then(|template_id| i32::try_from(template_id).ok())
+                .ok_or_else(|| {
+                    TicketError::InvalidTicket(format!("template_id {}", template_id))
+                })?,
+        ),
+    };
+
+    let mut document = Value::Object(Default::default());
+    if let Some(template_id) = template_id {
+        let template = match get_single_template(template_id, connection) {
+            Ok(template) => template,
+            Err(Error::NotFound) => return Err(TicketError::UnknownTemplate(template_id)),
+            Err(err) => return Err(err.into()),
+        };
+        let submitted_title = submitted
+            .as_object_mut()
+            .and_then(|ticket| ticket.remove("title"));
+
+        document = json!({
+            "title": template.title_pattern.replace(
+                "{title}",
+                submitted_title.as_ref().and_then(Value::as_str).unwrap_or_default(),
+            ),
+            "body": template.body,
+            "labels": template.labels,
+            "status": template.status,
+            "assignees": template.assigned_user.into_iter().collect::<Vec<_>>(),
+        });
+    }
+
+    assigned_user_as_assignees(&mut submitted);
+    apply_merge_patch(&mut document, &submitted);
+
+    serde_json::from_value(document).map_err(|err| TicketError::InvalidTicket(err.to_string()))
+}
+
+/// Applies a JSON Merge Patch to a ticket, fields that are left out of the patch stay as they are.
+pub fn patch_ticket(
+    connection: &mut SqliteConnection,
+    patch: &Value,
+    ticket_id: i32,
+    actor_id: i32,
+    expected_versions: Option<&[i32]>,
+) -> Result<Ticket, TicketError> {
+    connection.transaction(|connection| {
+        let old_ticket = get_single_ticket(ticket_id, connection)?;
+        let old_ticket = hydrate_ticket(connection, &old_ticket)?;
+
+        let mut document = serde_json::to_value(TicketPayload::from_ticket(&old_ticket))
+            .map_err(|err| TicketError::InvalidPatch(err.to_string()))?;
+        // only "assignees" describes the current assignment, "assigned_user" in a patch is mapped onto it
+        if let Value::Object(fields) = &mut document {
+            fields.remove("assigned_user");
+        }
+        if let Some(field) = null_without_default(patch) {
+            return Err(TicketError::InvalidPatch(format!(
+                "{} can't be null",
+                field
+            )));
+        }
+        let mut patch = patch.clone();
+        assigned_user_as_assignees(&mut patch);
+        null_lists_as_empty(&mut patch);
+        apply_merge_patch(&mut document, &patch);
+
+        let ticket: TicketPayload = serde_json::from_value(document)
+            .map_err(|err| TicketError::InvalidPatch(err.to_string()))?;
+
+        edit_ticket(
+            connection,
+            Json(ticket),
+            ticket_id,
+            actor_id,
+            expected_versions,
+        )
+    })
+}
+
+pub fn add_watcher(
+    connection: &mut SqliteConnection,
+    ticket_id: i32,
+    watcher_id: i32,
+) -> QueryResult<usize> {
+    connection.transaction(|connection| {
+        let added = diesel::insert_or_ignore_into(ticket_watchers::table)
+            .values((
+                ticket_watchers::ticket_id.eq(ticket_id),
+                ticket_watchers::user_id.eq(watcher_id),
+            ))
+            .execute(connection)?;
+        if added > 0 {
+            increment_version(connection, ticket_id)?;
+        }
+
+        Ok(added)
+    })
+}
+
+pub fn remove_watcher(
+    connection: &mut SqliteConnection,
+    ticket_id: i32,
+    watcher_id: i32,
+) -> QueryResult<usize> {
+    connection.transaction(|connection| {
+        let removed = diesel::delete(
+            ticket_watchers::table
+                .filter(ticket_watchers::ticket_id.eq(ticket_id))
+                .filter(ticket_watchers::user_id.eq(watcher_id)),
+        )
+        .execute(connection)?;
+        if removed > 0 {
+            increment_version(connection, ticket_id)?;
+        }
+
+        Ok(removed)
+    })
+}
+
+pub fn get_ticket_history(
+    ticket_id: i32,
+    connection: &mut SqliteConnection,
+) -> QueryResult<Vec<HistoryEntry>> {
+    ticket_history::table
+        .filter(ticket_history::ticket_id.eq(ticket_id))
+        .order(ticket_history::id)
+        .load::<HistoryEntry>(connection)
+}
+
+pub fn get_ticket_comments(
+    ticket_id: i32,
+    connection: &mut SqliteConnection,
+) -> QueryResult<Vec<Comment>> {
+    ticket_comments::table
+        .filter(ticket_comments::ticket_id.eq(ticket_id))
+        .order(ticket_comments::id)
+        .load::<Comment>(connection)
+}
+
+pub fn get_ticket_comment(
+    ticket_id: i32,
+    comment_id: i32,
+    connection: &mut SqliteConnection,
+) -> QueryResult<Comment> {
+    ticket_comments::table
+        .filter(ticket_comments::id.eq(comment_id))
+        .filter(ticket_comments::ticket_id.eq(ticket_id))
+        .get_result(connection)
+}
+
+pub fn create_ticket_comment(
+    connection: &mut SqliteConnection,
+    comment: Json<CommentPayload>,
+    ticket_id: i32,
+    author_id: i32,
+) -> QueryResult<Comment> {
+    let now_in_millis = current_time_in_millis();
+    let new_comment = NewComment {
+        ticket_id,
+        author_id,
+        parent_comment_id: comment.parent_comment_id,
+        body: comment.body.clone(),
+        created: now_in_millis.clone(),
+        last_modified: now_in_millis,
+    };
+
+    diesel::insert_into(ticket_comments::table)
+        .values(&new_comment)
+        .get_result(connection)
 }
 
-impl DataBase {
-    pub fn new() -> Self {
-        dotenv().ok();
+pub fn edit_ticket_comment(
+    connection: &mut SqliteConnection,
+    comment: Json<CommentPayload>,
+    comment_id: i32,
+) -> QueryResult<Comment> {
+    // replies can't be moved to another thread, so only the body is updated
+    diesel::update(ticket_comments::table.filter(ticket_comments::id.eq(comment_id)))
+        .set((
+            ticket_comments::body.eq(&comment.body),
+            ticket_comments::last_modified.eq(current_time_in_millis()),
+        ))
+        .get_result(connection)
+}
 
-        #[cfg(test)]
-        let variable_name = "TEST_DATABASE_URL";
+pub fn delete_ticket_comment(
+    connection: &mut SqliteConnection,
+    comment_id: i32,
+) -> QueryResult<Comment> {
+    connection.transaction(|connection| {
+        // replies would be left without their parent, so the whole thread below it goes too
+        let mut thread = vec![comment_id];
+        let mut parents = vec![comment_id];
 
-        #[cfg(not(test))]
-        let variable_name = "DATABASE_URL";
+        while !parents.is_empty() {
+            parents = ticket_comments::table
+                .filter(ticket_comments::parent_comment_id.eq_any(&parents))
+                .select(ticket_comments::id)
+                .load::<i32>(connection)?;
+            thread.extend(&parents);
+        }
 
-        let database_url = env::var(variable_name).expect("Could not find database url in .env");
+        let deleted_comment =
+            diesel::delete(ticket_comments::table.filter(ticket_comments::id.eq(comment_id)))
+                .get_result(connection)?;
+        diesel::delete(ticket_comments::table.filter(ticket_comments::id.eq_any(&thread)))
+            .execute(connection)?;
 
-        DataBase {
-            connection: SqliteConnection::establish(&database_url)
-                .unwrap_or_else(|_| panic!("Error connecting to {}", database_url)),
-        }
-    }
+        Ok(deleted_comment)
+    })
 }
 
-pub fn create_ticket(
+pub fn get_ticket_worklogs(
+    ticket_id: i32,
     connection: &mut SqliteConnection,
-    new_ticket: Json<TicketPayload>,
-) -> QueryResult<SqliteTicket> {
-    use crate::schema::tickets;
+) -> QueryResult<Vec<Worklog>> {
+    worklogs::table
+        .filter(worklogs::ticket_id.eq(ticket_id))
+        .order((worklogs::date, worklogs::id))
+        .load::<Worklog>(connection)
+}
 
-    let now_in_millis = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or(Duration::new(0, 0));
-    let new_ticket = NewTicket {
-        title: new_ticket.title.clone(),
-        body: new_ticket.body.clone(),
-        created: now_in_millis.as_millis().to_string(),
-        last_modified: now_in_millis.as_millis().to_string(),
-        labels: serde_json::to_string(&new_ticket.labels).unwrap(),
-        assigned_user: new_ticket.assigned_user,
-        // status is required to be sent by user to not have ugly null handling in update function,
-        // so even if user sends "Closed", set it to open.
-        // Makes not sense to create a closed ticket.
-        status: Status::Open.to_string(),
+pub fn get_ticket_worklog(
+    ticket_id: i32,
+    worklog_id: i32,
+    connection: &mut SqliteConnection,
+) -> QueryResult<Worklog> {
+    worklogs::table
+        .filter(worklogs::id.eq(worklog_id))
+        .filter(worklogs::ticket_id.eq(ticket_id))
+        .get_result(connection)
+}
+
+pub fn add_worklog(
+    connection: &mut SqliteConnection,
+    worklog: Json<WorklogPayload>,
+    ticket_id: i32,
+    author_id: i32,
+) -> QueryResult<Worklog> {
+    let worklog = worklog.into_inner();
+    let new_worklog = NewWorklog {
+        ticket_id,
+        user_id: author_id,
+        minutes: worklog.minutes,
+        date: worklog.date.unwrap_or_else(today),
+        note: worklog.note,
+        created: current_time_in_millis(),
     };
 
-    diesel::insert_into(tickets::table)
-        .values(&new_ticket)
+    diesel::insert_into(worklogs::table)
+        .values(&new_worklog)
+        .get_result(connection)
+}
+
+pub fn update_worklog(
+    connection: &mut SqliteConnection,
+    worklog: Json<WorklogPayload>,
+    old_worklog: &Worklog,
+) -> QueryResult<Worklog> {
+    let worklog = worklog.into_inner();
+
+    // the work stays on the day it was logged for, unless a new day is given
+    diesel::update(worklogs::table.find(old_worklog.id))
+        .set((
+            worklogs::minutes.eq(worklog.minutes),
+            worklogs::date.eq(worklog.date.unwrap_or_else(|| old_worklog.date.clone())),
+            worklogs::note.eq(worklog.note),
+        ))
         .get_result(connection)
 }
 
-pub fn get_all_tickets(connection: &mut SqliteConnection) -> QueryResult<Vec<SqliteTicket>> {
-    tickets.load::<SqliteTicket>(connection)
+pub fn remove_worklog(connection: &mut SqliteConnection, worklog_id: i32) -> QueryResult<Worklog> {
+    diesel::delete(worklogs::table.find(worklog_id)).get_result(connection)
 }
 
-pub fn get_single_ticket(
+pub fn get_worklog_summary(
+    connection: &mut SqliteConnection,
+    worklog_query: &WorklogQuery,
+) -> QueryResult<WorklogSummary> {
+    let mut query = worklogs::table
+        .select((worklogs::ticket_id, worklogs::user_id, worklogs::minutes))
+        .into_boxed();
+    if let Some(from) = &worklog_query.from {
+        query = query.filter(worklogs::date.ge(from));
+    }
+    if let Some(to) = &worklog_query.to {
+        query = query.filter(worklogs::date.le(to));
+    }
+    if let Some(worklog_ticket_id) = worklog_query.ticket_id {
+        query = query.filter(worklogs::ticket_id.eq(worklog_ticket_id));
+    }
+    if let Some(worklog_user_id) = worklog_query.user_id {
+        query = query.filter(worklogs::user_id.eq(worklog_user_id));
+    }
+    let logged_work = query.load::<(i32, i32, i32)>(connection)?;
+
+    let mut ticket_totals: BTreeMap<i32, i64> = BTreeMap::new();
+    let mut user_totals: BTreeMap<i32, i64> = BTreeMap::new();
+    for (worklog_ticket_id, worklog_user_id, minutes) in &logged_work {
+        *ticket_totals.entry(*worklog_ticket_id).or_default() += *minutes as i64;
+        *user_totals.entry(*worklog_user_id).or_default() += *minutes as i64;
+    }
+
+    Ok(WorklogSummary {
+        from: worklog_query.from.clone(),
+        to: worklog_query.to.clone(),
+        total_minutes: ticket_totals.values().sum(),
+        tickets: ticket_totals
+            .into_iter()
+            .map(|(ticket_id, minutes)| TicketWorklogTotal { ticket_id, minutes })
+            .collect(),
+        users: user_totals
+            .into_iter()
+            .map(|(total_user_id, minutes)| UserWorklogTotal {
+                user_id: total_user_id,
+                minutes,
+            })
+            .collect(),
+    })
+}
+
+pub fn get_ticket_links(
     ticket_id: i32,
     connection: &mut SqliteConnection,
-) -> QueryResult<SqliteTicket> {
-    tickets.filter(id.eq(ticket_id)).get_result(connection)
+) -> QueryResult<Vec<TicketLink>> {
+    ticket_links::table
+        .filter(
+            ticket_links::source_ticket_id
+                .eq(ticket_id)
+                .or(ticket_links::target_ticket_id.eq(ticket_id)),
+        )
+        // links to trashed tickets come back when the ticket is restored
+        .filter(ticket_links::source_ticket_id.ne_all(trashed_ticket_ids()))
+        .filter(ticket_links::target_ticket_id.ne_all(trashed_ticket_ids()))
+        .order(ticket_links::id)
+        .load::<TicketLink>(connection)
 }
 
-pub fn delete_ticket(
+fn trashed_ticket_ids() -> Select<Filter<tickets, IsNotNull<deleted_at>>, id> {
+    tickets.filter(deleted_at.is_not_null()).select(id)
+}
+
+pub fn get_ticket_link(
+    ticket_id: i32,
+    link_id: i32,
     connection: &mut SqliteConnection,
-    ticked_id: i32,
-) -> QueryResult<SqliteTicket> {
-    diesel::delete(tickets.filter(id.eq(ticked_id))).get_result(connection)
+) -> QueryResult<TicketLink> {
+    ticket_links::table
+        .filter(ticket_links::id.eq(link_id))
+        .filter(
+            ticket_links::source_ticket_id
+                .eq(ticket_id)
+                .or(ticket_links::target_ticket_id.eq(ticket_id)),
+        )
+        .get_result(connection)
 }
 
-pub fn edit_ticket(
+/// Whether `to` can be reached from `from` by following links of `link_type` in their stored direction.
+fn is_reachable(
     connection: &mut SqliteConnection,
-    ticket: Json<TicketPayload>,
+    from: i32,
+    to: i32,
+    link_type: LinkType,
+) -> QueryResult<bool> {
+    let mut visited = vec![from];
+    let mut current = vec![from];
+
+    while !current.is_empty() {
+        let next: Vec<i32> = ticket_links::table
+            .filter(ticket_links::link_type.eq(link_type.to_string()))
+            .filter(ticket_links::source_ticket_id.eq_any(&current))
+            .select(ticket_links::target_ticket_id)
+            .load::<i32>(connection)?;
+
+        if next.contains(&to) {
+            return Ok(true);
+        }
+
+        current = next
+            .into_iter()
+            .filter(|ticket_id| !visited.contains(ticket_id))
+            .collect();
+        visited.extend(&current);
+    }
+
+    Ok(false)
+}
+
+pub fn create_ticket_link(
+    connection: &mut SqliteConnection,
+    link: Json<LinkPayload>,
     ticket_id: i32,
-) -> QueryResult<SqliteTicket> {
-    let now_in_millis = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or(Duration::new(0, 0));
+) -> Result<TicketLink, LinkError> {
+    if link.ticket_id == ticket_id {
+        return Err(LinkError::SelfLink);
+    }
 
-    diesel::update(tickets.filter(id.eq(ticket_id)))
+    connection.transaction(|connection| {
+        get_single_ticket(ticket_id, connection)?;
+        if let Err(err) = get_single_ticket(link.ticket_id, connection) {
+            return Err(match err {
+                Error::NotFound => LinkError::UnknownTicket(link.ticket_id),
+                _ => LinkError::Database(err),
+            });
+        }
+
+        let (source, target, link_type) = if link.link_type.is_stored() {
+            (ticket_id, link.ticket_id, link.link_type)
+        } else {
+            (link.ticket_id, ticket_id, link.link_type.inverse())
+        };
+
+        let existing_links: i64 = ticket_links::table
+            .filter(
+                ticket_links::source_ticket_id
+                    .eq(source)
+                    .and(ticket_links::target_ticket_id.eq(target))
+                    .or(ticket_links::source_ticket_id
+                        .eq(target)
+                        .and(ticket_links::target_ticket_id.eq(source))),
+            )
+            .filter(ticket_links::link_type.eq(link_type.to_string()))
+            .count()
+            .get_result(connection)?;
+        if existing_links > 0 {
+            return Err(LinkError::AlreadyLinked);
+        }
+
+        if link_type == LinkType::ParentOf {
+            let parents: i64 = ticket_links::table
+                .filter(ticket_links::link_type.eq(LinkType::ParentOf.to_string()))
+                .filter(ticket_links::target_ticket_id.eq(target))
+                .count()
+                .get_result(connection)?;
+            if parents > 0 {
+                return Err(LinkError::AlreadyHasParent);
+            }
+        }
+
+        if link_type.forbids_cycles() && is_reachable(connection, target, source, link_type)? {
+            return Err(LinkError::Cycle);
+        }
+
+        let new_link = NewTicketLink {
+            source_ticket_id: source,
+            target_ticket_id: target,
+            link_type: link_type.to_string(),
+        };
+
+        Ok(diesel::insert_into(ticket_links::table)
+            .values(&new_link)
+            .get_result(connection)?)
+    })
+}
+
+pub fn remove_ticket_link(
+    connection: &mut SqliteConnection,
+    link_id: i32,
+) -> QueryResult<TicketLink> {
+    diesel::delete(ticket_links::table.filter(ticket_links::id.eq(link_id))).get_result(connection)
+}
+
+pub fn get_all_projects(connection: &mut SqliteConnection) -> QueryResult<Vec<Project>> {
+    projects::table
+        .order(projects::id)
+        .load::<Project>(connection)
+}
+
+pub fn get_project_by_key(
+    project_key: &str,
+    connection: &mut SqliteConnection,
+) -> QueryResult<Project> {
+    projects::table
+        .filter(projects::key.eq(project_key))
+        .get_result(connection)
+}
+
+/// Tickets that are created without a project go into the oldest one.
+pub fn get_default_project(connection: &mut SqliteConnection) -> QueryResult<Project> {
+    projects::table.order(projects::id).first(connection)
+}
+
+pub fn add_project(
+    connection: &mut SqliteConnection,
+    new_project: Json<NewProject>,
+) -> QueryResult<Project> {
+    diesel::insert_into(projects::table)
+        .values(new_project.into_inner())
+        .get_result(connection)
+}
+
+pub fn count_project_tickets(
+    ticket_project_id: i32,
+    connection: &mut SqliteConnection,
+) -> QueryResult<i64> {
+    tickets
+        .filter(project_id.eq(ticket_project_id))
+        .count()
+        .get_result(connection)
+}
+
+pub fn remove_project(
+    connection: &mut SqliteConnection,
+    project_id_to_delete: i32,
+) -> QueryResult<Project> {
+    diesel::delete(projects::table.find(project_id_to_delete)).get_result(connection)
+}
+
+pub fn get_all_milestones(connection: &mut SqliteConnection) -> QueryResult<Vec<Milestone>> {
+    milestones::table
+        .order(milestones::id)
+        .load::<Milestone>(connection)
+}
+
+pub fn get_single_milestone(
+    milestone_id_to_find: i32,
+    connection: &mut SqliteConnection,
+) -> QueryResult<Milestone> {
+    milestones::table
+        .find(milestone_id_to_find)
+        .get_result(connection)
+}
+
+pub fn add_milestone(
+    connection: &mut SqliteConnection,
+    milestone: Json<MilestonePayload>,
+) -> QueryResult<Milestone> {
+    diesel::insert_into(milestones::table)
+        .values(NewMilestone::from(milestone.into_inner()))
+        .get_result(connection)
+}
+
+pub fn update_milestone(
+    connection: &mut SqliteConnection,
+    milestone: Json<MilestonePayload>,
+    milestone_id_to_update: i32,
+) -> QueryResult<Milestone> {
+    diesel::update(milestones::table.find(milestone_id_to_update))
+        .set(NewMilestone::from(milestone.into_inner()))
+        .get_result(connection)
+}
+
+pub fn remove_milestone(
+    connection: &mut SqliteConnection,
+    milestone_id_to_delete: i32,
+) -> QueryResult<Milestone> {
+    connection.transaction(|connection| {
+        // tickets of the milestone stay, they just aren't planned for any milestone anymore
+        diesel::update(tickets.filter(milestone_id.eq(milestone_id_to_delete)))
+            .set((milestone_id.eq(None::<i32>), version.eq(version + 1)))
+            .execute(connection)?;
+
+        diesel::delete(milestones::table.find(milestone_id_to_delete)).get_result(connection)
+    })
+}
+
+pub fn get_all_templates(connection: &mut SqliteConnection) -> QueryResult<Vec<TicketTemplate>> {
+    Ok(ticket_templates::table
+        .order(ticket_templates::id)
+        .load::<SqliteTicketTemplate>(connection)?
+        .into_iter()
+        .map(TicketTemplate::from)
+        .collect())
+}
+
+pub fn get_single_template(
+    template_id: i32,
+    connection: &mut SqliteConnection,
+) -> QueryResult<TicketTemplate> {
+    ticket_templates::table
+        .find(template_id)
+        .get_result::<SqliteTicketTemplate>(connection)
+        .map(TicketTemplate::from)
+}
+
+pub fn add_template(
+    connection: &mut SqliteConnection,
+    template: Json<TemplatePayload>,
+) -> QueryResult<TicketTemplate> {
+    diesel::insert_into(ticket_templates::table)
+        .values(NewTicketTemplate::from(template.into_inner()))
+        .get_result::<SqliteTicketTemplate>(connection)
+        .map(TicketTemplate::from)
+}
+
+pub fn update_template(
+    connection: &mut SqliteConnection,
+    template: Json<TemplatePayload>,
+    template_id: i32,
+) -> QueryResult<TicketTemplate> {
+    diesel::update(ticket_templates::table.find(template_id))
+        .set(NewTicketTemplate::from(template.into_inner()))
+        .get_result::<SqliteTicketTemplate>(connection)
+        .map(TicketTemplate::from)
+}
+
+pub fn remove_template(
+    connection: &mut SqliteConnection,
+    template_id: i32,
+) -> QueryResult<TicketTemplate> {
+    diesel::delete(ticket_templates::table.find(template_id))
+        .get_result::<SqliteTicketTemplate>(connection)
+        .map(TicketTemplate::from)
+}
+
+/// Filters of `owner_id` and those other users shared.
+pub fn get_visible_saved_filters(
+    connection: &mut SqliteConnection,
+    owner_id: i32,
+) -> QueryResult<Vec<SavedFilter>> {
+    saved_filters::table
+        .filter(
+            saved_filters::owner_id
+                .eq(owner_id)
+                .or(saved_filters::shared),
+        )
+        .order(saved_filters::id)
+        .load::<SqliteSavedFilter>(connection)?
+        .into_iter()
+        .map(saved_filter_from_row)
+        .collect()
+}
+
+/// A row whose filter isn't valid JSON fails like any other row that can't be read.
+fn saved_filter_from_row(saved_filter: SqliteSavedFilter) -> QueryResult<SavedFilter> {
+    SavedFilter::try_from(saved_filter).map_err(|err| Error::DeserializationError(Box::new(err)))
+}
+
+pub fn get_saved_filter(
+    saved_filter_id: i32,
+    connection: &mut SqliteConnection,
+) -> QueryResult<SavedFilter> {
+    saved_filters::table
+        .find(saved_filter_id)
+        .get_result::<SqliteSavedFilter>(connection)
+        .and_then(saved_filter_from_row)
+}
+
+pub fn add_saved_filter(
+    connection: &mut SqliteConnection,
+    saved_filter: Json<SavedFilterPayload>,
+    owner_id: i32,
+) -> QueryResult<SavedFilter> {
+    let saved_filter = saved_filter.into_inner();
+    let now_in_millis = current_time_in_millis();
+    let new_saved_filter = NewSavedFilter {
+        owner_id,
+        name: saved_filter.name,
+        filter: saved_filter.filter.to_string(),
+        shared: saved_filter.shared,
+        created: now_in_millis.clone(),
+        last_modified: now_in_millis,
+    };
+
+    diesel::insert_into(saved_filters::table)
+        .values(&new_saved_filter)
+        .get_result::<SqliteSavedFilter>(connection)
+        .and_then(saved_filter_from_row)
+}
+
+pub fn update_saved_filter(
+    connection: &mut SqliteConnection,
+    saved_filter: Json<SavedFilterPayload>,
+    saved_filter_id: i32,
+) -> QueryResult<SavedFilter> {
+    let saved_filter = saved_filter.into_inner();
+
+    diesel::update(saved_filters::table.find(saved_filter_id))
         .set((
-            title.eq(&ticket.title),
-            body.eq(&ticket.body),
-            labels.eq(serde_json::to_string(&ticket.labels).unwrap()),
-            last_modified.eq(now_in_millis.as_millis().to_string()),
-            status.eq(ticket.status.to_string()),
+            saved_filters::name.eq(saved_filter.name),
+            saved_filters::filter.eq(saved_filter.filter.to_string()),
+            saved_filters::shared.eq(saved_filter.shared),
+            saved_filters::last_modified.eq(current_time_in_millis()),
         ))
+        .get_result::<SqliteSavedFilter>(connection)
+        .and_then(saved_filter_from_row)
+}
+
+pub fn remove_saved_filter(
+    connection: &mut SqliteConnection,
+    saved_filter_id: i32,
+) -> QueryResult<SavedFilter> {
+    diesel::delete(saved_filters::table.find(saved_filter_id))
+        .get_result::<SqliteSavedFilter>(connection)
+        .and_then(saved_filter_from_row)
+}
+
+pub fn get_burndown(
+    connection: &mut SqliteConnection,
+    burndown_query: &BurndownQuery,
+    from: Date,
+    to: Date,
+) -> QueryResult<Burndown> {
+    let closed_statuses = get_closed_status_names(connection)?;
+    let history = ticket_history::table
+        .filter(ticket_history::field.eq_any([
+            "status",
+            "story_points",
+            "milestone_id",
+            "deleted_at",
+        ]))
+        .order(ticket_history::id)
+        .load::<HistoryEntry>(connection)?;
+
+    Ok(Burndown {
+        from: burndown_query.from.clone(),
+        to: burndown_query.to.clone(),
+        milestone_id: burndown_query.milestone_id,
+        days: burndown_days(
+            &history,
+            &closed_statuses,
+            from,
+            to,
+            burndown_query.milestone_id,
+        ),
+    })
+}
+
+pub fn get_milestone_summary(
+    milestone_id_to_summarize: i32,
+    connection: &mut SqliteConnection,
+) -> QueryResult<MilestoneSummary> {
+    let milestone = get_single_milestone(milestone_id_to_summarize, connection)?;
+    let closed_statuses = get_closed_status_names(connection)?;
+    let ticket_statuses = tickets
+        .filter(milestone_id.eq(milestone_id_to_summarize))
+        .filter(deleted_at.is_null())
+        .select(status)
+        .load::<String>(connection)?;
+
+    let total_tickets = ticket_statuses.len() as i64;
+    let closed_tickets = ticket_statuses
+        .iter()
+        .filter(|ticket_status| closed_statuses.contains(ticket_status))
+        .count() as i64;
+
+    Ok(MilestoneSummary {
+        milestone,
+        total_tickets,
+        open_tickets: total_tickets - closed_tickets,
+        closed_tickets,
+        progress: if total_tickets > 0 {
+            closed_tickets * 100 / total_tickets
+        } else {
+            0
+        },
+    })
+}
+
+pub fn get_ticket_attachments(
+    ticket_id: i32,
+    connection: &mut SqliteConnection,
+) -> QueryResult<Vec<Attachment>> {
+    attachments::table
+        .filter(attachments::ticket_id.eq(ticket_id))
+        .order(attachments::id)
+        .load::<Attachment>(connection)
+}
+
+pub fn get_ticket_attachment(
+    ticket_id: i32,
+    attachment_id: i32,
+    connection: &mut SqliteConnection,
+) -> QueryResult<Attachment> {
+    attachments::table
+        .filter(attachments::id.eq(attachment_id))
+        .filter(attachments::ticket_id.eq(ticket_id))
+        .get_result(connection)
+}
+
+pub fn add_attachment(
+    connection: &mut SqliteConnection,
+    upload: &Upload,
+    ticket_id: i32,
+    uploader_id: i32,
+) -> QueryResult<Attachment> {
+    let new_attachment = NewAttachment {
+        ticket_id,
+        uploader_id,
+        file_name: upload.file_name.clone(),
+        mime_type: upload.mime_type.clone(),
+        size: upload.content.len() as i32,
+        checksum: checksum(&upload.content),
+        created: current_time_in_millis(),
+    };
+
+    diesel::insert_into(attachments::table)
+        .values(&new_attachment)
+        .get_result(connection)
+}
+
+pub fn remove_attachment(
+    connection: &mut SqliteConnection,
+    attachment_id: i32,
+) -> QueryResult<Attachment> {
+    diesel::delete(attachments::table.filter(attachments::id.eq(attachment_id)))
+        .get_result(connection)
+}
+
+pub fn get_all_labels(connection: &mut SqliteConnection) -> QueryResult<Vec<Label>> {
+    all_labels.order(label_id).load::<Label>(connection)
+}
+
+pub fn get_label_by_name(name: &str, connection: &mut SqliteConnection) -> QueryResult<Label> {
+    all_labels
+        .filter(label_name.eq(name))
+        .get_result(connection)
+}
+
+pub fn add_label(
+    connection: &mut SqliteConnection,
+    new_label: Json<NewLabel>,
+) -> QueryResult<Label> {
+    diesel::insert_into(all_labels)
+        .values(new_label.into_inner())
+        .get_result(connection)
+}
+
+pub fn update_label(
+    connection: &mut SqliteConnection,
+    label: Json<NewLabel>,
+    updated_label_id: i32,
+) -> QueryResult<Label> {
+    diesel::update(all_labels.filter(label_id.eq(updated_label_id)))
+        .set(label.into_inner())
+        .get_result(connection)
+}
+
+pub fn remove_label(
+    connection: &mut SqliteConnection,
+    removed_label_id: i32,
+) -> QueryResult<Label> {
+    connection.transaction(|connection| {
+        // foreign keys aren't enforced, so nothing else would remove the label from its tickets
+        diesel::delete(ticket_labels::table.filter(ticket_labels::label_id.eq(removed_label_id)))
+            .execute(connection)?;
+        let removed_label = diesel::delete(all_labels.filter(label_id.eq(removed_label_id)))
+            .get_result(connection)?;
+
+        Ok(removed_label)
+    })
+}
+
+pub fn get_all_statuses(connection: &mut SqliteConnection) -> QueryResult<Vec<Status>> {
+    all_statuses.order(status_id).load::<Status>(connection)
+}
+
+pub fn get_status_by_name(name: &str, connection: &mut SqliteConnection) -> QueryResult<Status> {
+    all_statuses
+        .filter(status_name.eq(name))
+        .get_result(connection)
+}
+
+pub fn get_single_status(
+    requested_status_id: i32,
+    connection: &mut SqliteConnection,
+) -> QueryResult<Status> {
+    all_statuses
+        .filter(status_id.eq(requested_status_id))
+        .get_result(connection)
+}
+
+pub fn get_closed_status_names(connection: &mut SqliteConnection) -> QueryResult<Vec<String>> {
+    all_statuses
+        .filter(is_closed.eq(true))
+        .select(status_name)
+        .load::<String>(connection)
+}
+
+pub fn add_status(
+    connection: &mut SqliteConnection,
+    new_status: Json<NewStatus>,
+) -> QueryResult<Status> {
+    diesel::insert_into(all_statuses)
+        .values(new_status.into_inner())
+        .get_result(connection)
+}
+
+pub fn count_tickets_with_status(
+    ticket_status: &str,
+    connection: &mut SqliteConnection,
+) -> QueryResult<i64> {
+    tickets
+        .filter(status.eq(ticket_status))
+        .count()
+        .get_result(connection)
+}
+
+pub fn remove_status(
+    connection: &mut SqliteConnection,
+    removed_status_id: i32,
+) -> QueryResult<Status> {
+    connection.transaction(|connection| {
+        let removed_status: Status =
+            diesel::delete(all_statuses.filter(status_id.eq(removed_status_id)))
+                .get_result(connection)?;
+
+        diesel::delete(
+            status_transitions::table.filter(
+                status_transitions::from_status
+                    .eq(&removed_status.name)
+                    .or(status_transitions::to_status.eq(&removed_status.name)),
+            ),
+        )
+        .execute(connection)?;
+
+        Ok(removed_status)
+    })
+}
+
+pub fn get_all_transitions(
+    connection: &mut SqliteConnection,
+) -> QueryResult<Vec<StatusTransition>> {
+    status_transitions::table
+        .order(status_transitions::id)
+        .load::<StatusTransition>(connection)
+}
+
+pub fn get_transition(
+    from: &str,
+    to: &str,
+    connection: &mut SqliteConnection,
+) -> QueryResult<StatusTransition> {
+    status_transitions::table
+        .filter(status_transitions::from_status.eq(from))
+        .filter(status_transitions::to_status.eq(to))
+        .get_result(connection)
+}
+
+pub fn add_transition(
+    connection: &mut SqliteConnection,
+    new_transition: Json<NewStatusTransition>,
+) -> QueryResult<StatusTransition> {
+    diesel::insert_into(status_transitions::table)
+        .values(new_transition.into_inner())
+        .get_result(connection)
+}
+
+pub fn remove_transition(
+    connection: &mut SqliteConnection,
+    transition_id: i32,
+) -> QueryResult<StatusTransition> {
+    diesel::delete(status_transitions::table.filter(status_transitions::id.eq(transition_id)))
         .get_result(connection)
 }
 
@@ -153,34 +1970,63 @@ pub fn filter_tickets_in_database(
     connection: &mut SqliteConnection,
     filter_payload: Json<FilterPayload>,
 ) -> Result<Vec<Ticket>, ()> {
+    // title search is a plain substring match, search_tickets is the full-text search over title and body
+    let closed_statuses = get_closed_status_names(connection).map_err(|_| ())?;
+
+    filter_tickets_query(&filter_payload, &closed_statuses, &today())
+        .load::<SqliteTicket>(connection)
+        .and_then(|filtered_tickets| hydrate_tickets(connection, &filtered_tickets))
+        .map_err(|_| ())
+}
+
+/// Searches title and body with the FTS5 index, matches in the title count more than in the body.
+pub fn search_tickets(
+    connection: &mut SqliteConnection,
+    match_expression: &str,
+    limit: i64,
+) -> QueryResult<Vec<SearchResult>> {
     /*
-     * It's probably a horrible idea to fetch all data from the database an then map and filter through each and every entry,
-     * but SQLite does not support arrays, so we would have to use LIKE for the labels which would be almost equally as slow.
-     * Plus it would lead to horrible string interpolation with the %-pattern.
-     *
-     * An alternative would of course be FTS, but I couldn't get it to work with diesel. From my understanding,
-     * diesel needs us to specify a primary key, but FTS does that automatically and doesn't want us to do it ourselves.
+     * diesel's table! macro needs a primary key, but FTS tables only have the implicit rowid
+     * and don't want us to declare one ourselves, so the index is queried with raw sql instead.
+     * bm25 ranks better matches lower, so it is negated to get a score where higher is better.
      */
-    return match tickets.load::<SqliteTicket>(connection) {
-        Ok(all_tickets) => {
-            let parsed_tickets: Vec<Ticket> = all_tickets
-                .iter()
-                .map(|sqlite_ticket| sqlite_ticket.to_ticket())
-                .collect();
-
-            Ok(parsed_tickets
-                .iter()
-                .filter(|t| {
-                    filter_by_title(&filter_payload.title, t)
-                        && filter_by_assigned_user(filter_payload.assigned_user, t)
-                        && filter_by_labels(&filter_payload.labels, t)
-                        && filter_by_status(filter_payload.status, t)
+    let hits = diesel::sql_query(
+        "select tickets_search.rowid as ticket_id,
+            highlight(tickets_search, 0, '<mark>', '</mark>') as title,
+            snippet(tickets_search, 1, '<mark>', '</mark>', '…', 16) as snippet,
+            -bm25(tickets_search, 10.0, 1.0) as score
+        from tickets_search
+        join tickets on tickets.id = tickets_search.rowid
+        where tickets_search match ? and tickets.deleted_at is null
+        order by score desc, tickets.id
+        limit ?",
+    )
+    .bind::<Text, _>(match_expression)
+    .bind::<BigInt, _>(limit)
+    .load::<SearchHit>(connection)?;
+
+    let hit_ids: Vec<i32> = hits.iter().map(|hit| hit.ticket_id).collect();
+    let matching_tickets = tickets
+        .filter(id.eq_any(&hit_ids))
+        .load::<SqliteTicket>(connection)?;
+    let mut tickets_by_id: HashMap<i32, Ticket> = hydrate_tickets(connection, &matching_tickets)?
+        .into_iter()
+        .map(|ticket| (ticket.id, ticket))
+        .collect();
+
+    Ok(hits
+        .into_iter()
+        .filter_map(|hit| {
+            tickets_by_id
+                .remove(&hit.ticket_id)
+                .map(|ticket| SearchResult {
+                    ticket,
+                    title: hit.title,
+                    snippet: hit.snippet,
+                    score: hit.score,
                 })
-                .cloned()
-                .collect::<Vec<_>>())
-        }
-        Err(_) => Err(()),
-    };
+        })
+        .collect())
 }
 
 pub fn write_session_to_db(new_session: NewSession, connection: &mut SqliteConnection) {
@@ -198,10 +2044,8 @@ pub fn remove_session_from_db(
 }
 
 pub fn session_in_db(session_token: String, connection: &mut SqliteConnection) -> bool {
-    matches!(
-        sessions
-            .filter(token.eq(session_token))
-            .get_result::<DatabaseSession>(connection),
-        Ok(_)
-    )
+    sessions
+        .filter(token.eq(session_token))
+        .get_result::<DatabaseSession>(connection)
+        .is_ok()
 }
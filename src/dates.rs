@@ -0,0 +1,31 @@
+use actix_web::cookie::time::{Date, Month, OffsetDateTime};
+
+/// Parses dates in the format YYYY-MM-DD, which is how dates are stored and sent.
+/// Dates in that format can be compared as strings, so queries don't have to parse them.
+pub fn parse_date(value: &str) -> Option<Date> {
+    let mut parts = value.splitn(3, '-');
+    let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+
+    // checked digit by digit, since parsing alone would also accept a sign like in `2026-01-+1`
+    let is_number = |part: &str, len| part.len() == len && part.bytes().all(|b| b.is_ascii_digit());
+    if !is_number(year, 4) || !is_number(month, 2) || !is_number(day, 2) {
+        return None;
+    }
+
+    let month = Month::try_from(month.parse::<u8>().ok()?).ok()?;
+
+    Date::from_calendar_date(year.parse().ok()?, month, day.parse().ok()?).ok()
+}
+
+pub fn is_valid_date(value: &str) -> bool {
+    parse_date(value).is_some()
+}
+
+/// Timestamps like `created` are stored as milliseconds since the epoch, this is when `day` starts in UTC.
+pub fn start_of_day_in_millis(day: Date) -> i64 {
+    day.midnight().assume_utc().unix_timestamp() * 1000
+}
+
+pub fn today() -> String {
+    OffsetDateTime::now_utc().date().to_string()
+}
@@ -1,29 +1,251 @@
-use crate::models::{Label, Status, Ticket};
+use crate::dates::{parse_date, start_of_day_in_millis};
+use crate::models::Ticket;
+use crate::payloads::{AssigneeMatch, FilterPayload, TicketSort};
+use crate::schema::ticket_assignees;
+use crate::schema::ticket_labels;
+use crate::schema::tickets;
+use crate::search::SearchTerms;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Bool, Integer, Text};
+use diesel::sqlite::Sqlite;
+use serde_json::Value;
 
-pub fn filter_by_assigned_user(user_id: Option<i32>, ticket: &Ticket) -> bool {
-    match user_id {
-        Some(user_id) => ticket.assigned_user.unwrap_or(0) == user_id,
-        None => true,
+diesel::define_sql_function! {
+    /// Position of `needle` in `haystack` starting at 1, 0 if it isn't contained. Unlike LIKE, it is case-sensitive.
+    fn instr(haystack: Text, needle: Text) -> Integer;
+}
+
+/// Builds the query for the tickets matching `filter`, so only those are loaded from the database.
+/// Trashed tickets never match.
+pub fn filter_tickets_query<'a>(
+    filter: &FilterPayload,
+    closed_statuses: &[String],
+    today: &str,
+) -> tickets::BoxedQuery<'a, Sqlite> {
+    let mut query = tickets::table
+        .filter(tickets::deleted_at.is_null())
+        .into_boxed();
+
+    if let Some(title) = &filter.title {
+        query = query.filter(instr(tickets::title, title.clone()).gt(0));
+    }
+    if let Some(assigned_user) = filter.assigned_user {
+        query = query.filter(tickets::id.eq_any(assigned_tickets(vec![assigned_user])));
+    }
+    if let Some(assignees) = &filter.assignees {
+        match filter.assignee_match.unwrap_or_default() {
+            AssigneeMatch::Any => {
+                query = query.filter(tickets::id.eq_any(assigned_tickets(assignees.clone())));
+            }
+            AssigneeMatch::All => {
+                for assignee in assignees {
+                    query = query.filter(tickets::id.eq_any(assigned_tickets(vec![*assignee])));
+                }
+            }
+        }
+    }
+    for label in filter.labels.iter().flatten() {
+        query = query.filter(
+            tickets::id.eq_any(
+                ticket_labels::table
+                    .filter(ticket_labels::label_id.eq(*label))
+                    .select(ticket_labels::ticket_id),
+            ),
+        );
+    }
+    if let Some(exclude_labels) = &filter.exclude_labels {
+        query = query.filter(
+            tickets::id.ne_all(
+                ticket_labels::table
+                    .filter(ticket_labels::label_id.eq_any(exclude_labels.clone()))
+                    .select(ticket_labels::ticket_id),
+            ),
+        );
+    }
+    if let Some(exclude_assignees) = &filter.exclude_assignees {
+        query = query.filter(tickets::id.ne_all(assigned_tickets(exclude_assignees.clone())));
+    }
+    if let Some(text) = &filter.text {
+        let search_terms = SearchTerms::parse(text);
+        // the search index can't be declared with table!, so it is queried with plain sql
+        if let Some(match_expression) = search_terms.match_expression() {
+            query = query.filter(
+                sql::<Bool>(
+                    "tickets.id in (select rowid from tickets_search where tickets_search match ",
+                )
+                .bind::<Text, _>(match_expression)
+                .sql(")"),
+            );
+        } else if let Some(excluded_expression) = search_terms.excluded_expression() {
+            query = query.filter(
+                sql::<Bool>(
+                    "tickets.id not in (select rowid from tickets_search where tickets_search match ",
+                )
+                .bind::<Text, _>(excluded_expression)
+                .sql(")"),
+            );
+        }
+    }
+    if let Some(status) = &filter.status {
+        query = query.filter(tickets::status.eq(status.clone()));
+    }
+    if let Some(exclude_statuses) = &filter.exclude_statuses {
+        query = query.filter(tickets::status.ne_all(exclude_statuses.clone()));
+    }
+    if let Some(priority) = filter.priority {
+        query = query.filter(tickets::priority.eq(priority.rank()));
+    }
+    if let Some(severity) = filter.severity {
+        query = query.filter(tickets::severity.eq(severity.to_string()));
+    }
+    if let Some(milestone_id) = filter.milestone_id {
+        query = query.filter(tickets::milestone_id.eq(milestone_id));
+    }
+    if let Some(reporter_id) = filter.reporter_id {
+        query = query.filter(tickets::reporter_id.eq(reporter_id));
+    }
+    // a ticket is overdue if its due date has passed and it isn't closed yet
+    match filter.overdue {
+        Some(true) => {
+            query = query.filter(
+                tickets::due_date
+                    .lt(today.to_string())
+                    .and(tickets::status.ne_all(closed_statuses.to_vec())),
+            );
+        }
+        Some(false) => {
+            query = query.filter(
+                tickets::due_date
+                    .is_null()
+                    .or(tickets::due_date.ge(today.to_string()))
+                    .or(tickets::status.eq_any(closed_statuses.to_vec())),
+            );
+        }
+        None => {}
+    }
+    // comparing with null is never true, so tickets without a due date never match a due date filter
+    if let Some(due_before) = &filter.due_before {
+        query = query.filter(tickets::due_date.lt(due_before.clone()));
+    }
+    if let Some(due_after) = &filter.due_after {
+        query = query.filter(tickets::due_date.gt(due_after.clone()));
+    }
+    // like the due date filters, the bounds are exclusive and whole days
+    for (column, before, after) in [
+        ("created", &filter.created_before, &filter.created_after),
+        (
+            "last_modified",
+            &filter.updated_before,
+            &filter.updated_after,
+        ),
+    ] {
+        if let Some(before) = before.as_deref().and_then(parse_date) {
+            query = query.filter(millis_column_compared(
+                column,
+                "<",
+                start_of_day_in_millis(before),
+            ));
+        }
+        if let Some(after) = after
+            .as_deref()
+            .and_then(|after| parse_date(after)?.next_day())
+        {
+            query = query.filter(millis_column_compared(
+                column,
+                ">=",
+                start_of_day_in_millis(after),
+            ));
+        }
+    }
+
+    let created = || sql::<BigInt>("cast(tickets.created as integer)");
+    let updated = || sql::<BigInt>("cast(tickets.last_modified as integer)");
+    match filter.sort {
+        Some(TicketSort::Priority) => query.order((tickets::priority.asc(), tickets::id.asc())),
+        Some(TicketSort::PriorityDescending) => {
+            query.order((tickets::priority.desc(), tickets::id.asc()))
+        }
+        Some(TicketSort::Created) => query.order((created().asc(), tickets::id.asc())),
+        Some(TicketSort::CreatedDescending) => query.order((created().desc(), tickets::id.asc())),
+        Some(TicketSort::Updated) => query.order((updated().asc(), tickets::id.asc())),
+        Some(TicketSort::UpdatedDescending) => query.order((updated().desc(), tickets::id.asc())),
+        None => query.order(tickets::id.asc()),
     }
 }
 
-pub fn filter_by_title(ticket_title: &Option<String>, ticket: &Ticket) -> bool {
-    match ticket_title {
-        Some(ticket_title) => ticket.title.contains(ticket_title),
-        None => true,
+/// `created` and `last_modified` hold milliseconds as text, so they are cast to be compared as numbers.
+fn millis_column_compared(
+    column: &str,
+    operator: &str,
+    millis: i64,
+) -> Box<dyn BoxableExpression<tickets::table, Sqlite, SqlType = Bool>> {
+    Box::new(
+        sql::<Bool>(&format!(
+            "cast(tickets.{} as integer) {} ",
+            column, operator
+        ))
+        .bind::<BigInt, _>(millis),
+    )
+}
+
+/// Ids of the tickets at least one of `assignees` is assigned to.
+fn assigned_tickets(assignees: Vec<i32>) -> ticket_assignees::BoxedQuery<'static, Sqlite, Integer> {
+    ticket_assignees::table
+        .filter(ticket_assignees::user_id.eq_any(assignees))
+        .select(ticket_assignees::ticket_id)
+        .into_boxed()
+}
+
+/// Sorts by the requested order, tickets that are equal in that order stay sorted by id.
+pub fn sort_tickets(tickets: &mut [Ticket], sort: Option<TicketSort>) {
+    match sort {
+        Some(TicketSort::Priority) => tickets.sort_by_key(|ticket| ticket.priority),
+        Some(TicketSort::PriorityDescending) => {
+            tickets.sort_by_key(|ticket| std::cmp::Reverse(ticket.priority))
+        }
+        Some(TicketSort::Created) => tickets.sort_by_key(|ticket| millis(&ticket.created)),
+        Some(TicketSort::CreatedDescending) => {
+            tickets.sort_by_key(|ticket| std::cmp::Reverse(millis(&ticket.created)))
+        }
+        Some(TicketSort::Updated) => tickets.sort_by_key(|ticket| millis(&ticket.last_modified)),
+        Some(TicketSort::UpdatedDescending) => {
+            tickets.sort_by_key(|ticket| std::cmp::Reverse(millis(&ticket.last_modified)))
+        }
+        None => {}
     }
 }
 
-pub fn filter_by_labels(ticket_labels: &Option<Vec<Label>>, ticket: &Ticket) -> bool {
-    match ticket_labels {
-        Some(ticket_labels) => ticket_labels.iter().all(|l| ticket.labels.contains(l)),
-        None => true,
+fn millis(timestamp: &str) -> i64 {
+    timestamp.parse().unwrap_or_default()
+}
+
+/// Turns the definition of a saved filter into the `FilterPayload` it stands for when `me` runs it.
+/// User ids given as `"me"` are replaced with the id of `me`.
+pub fn resolve_saved_filter(definition: &Value, me: i32) -> serde_json::Result<FilterPayload> {
+    let mut definition = definition.clone();
+    if let Value::Object(fields) = &mut definition {
+        for field in [
+            "assigned_user",
+            "reporter_id",
+            "assignees",
+            "exclude_assignees",
+        ] {
+            match fields.get_mut(field) {
+                Some(Value::Array(user_ids)) => {
+                    user_ids.iter_mut().for_each(|id| resolve_me(id, me))
+                }
+                Some(user_id) => resolve_me(user_id, me),
+                None => {}
+            }
+        }
     }
+
+    serde_json::from_value(definition)
 }
 
-pub fn filter_by_status(ticket_status: Option<Status>, ticket: &Ticket) -> bool {
-    match ticket_status {
-        Some(ticket_status) => ticket_status == ticket.status,
-        None => true,
+fn resolve_me(user_id: &mut Value, me: i32) {
+    if user_id.as_str() == Some("me") {
+        *user_id = Value::from(me);
     }
 }
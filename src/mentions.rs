@@ -0,0 +1,51 @@
+use std::collections::BTreeSet;
+
+/// Ids of the users mentioned in `text` as `@display_name`, given the ids and display names of all users.
+/// Display names may contain spaces, so the longest display name following an `@` wins.
+/// An `@` directly after a letter or digit, like in an email address, is no mention.
+pub fn mentioned_users(text: &str, known_users: &[(i32, String)]) -> BTreeSet<i32> {
+    let mut mentioned = BTreeSet::new();
+
+    for (position, _) in text.match_indices('@') {
+        let preceded_by_word = text[..position]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_alphanumeric);
+        if preceded_by_word {
+            continue;
+        }
+
+        let rest = &text[position + 1..];
+        let longest_name = known_users
+            .iter()
+            .filter(|(_, name)| !name.is_empty() && is_mentioned_at_start(rest, name))
+            .map(|(_, name)| name.len())
+            .max();
+
+        if let Some(longest_name) = longest_name {
+            mentioned.extend(
+                known_users
+                    .iter()
+                    .filter(|(_, name)| {
+                        name.len() == longest_name && is_mentioned_at_start(rest, name)
+                    })
+                    .map(|(user_id, _)| *user_id),
+            );
+        }
+    }
+
+    mentioned
+}
+
+/// Whether `text` starts with `name`, ignoring ASCII case, and the name isn't just the beginning of a longer word.
+fn is_mentioned_at_start(text: &str, name: &str) -> bool {
+    let starts_with_name = text
+        .get(..name.len())
+        .is_some_and(|start| start.eq_ignore_ascii_case(name));
+
+    starts_with_name
+        && !text[name.len()..]
+            .chars()
+            .next()
+            .is_some_and(|next| next.is_alphanumeric() || next == '_')
+}
@@ -0,0 +1,24 @@
+use serde_json::Value;
+
+/// Applies a JSON Merge Patch (RFC 7396) to `target`.
+/// Members of the patch replace those of the target, `null` removes them and objects are merged recursively.
+pub fn apply_merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+
+    if !target.is_object() {
+        *target = Value::Object(Default::default());
+    }
+
+    if let Value::Object(target) = target {
+        for (name, value) in patch {
+            if value.is_null() {
+                target.remove(name);
+            } else {
+                apply_merge_patch(target.entry(name.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
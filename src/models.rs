@@ -1,6 +1,9 @@
+use crate::payloads::{MilestonePayload, TemplatePayload};
 use diesel::prelude::*;
 use diesel::{FromSqlRow, Queryable};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
 use std::str::FromStr;
 
 #[derive(Queryable, Deserialize, Serialize, Debug)]
@@ -12,9 +15,21 @@ pub struct SqliteTicket {
     pub body: String,
     pub created: String,
     pub last_modified: String,
-    pub labels: String,
-    pub assigned_user: Option<i32>,
     pub status: String,
+    pub priority: i32,
+    pub severity: String,
+    pub start_date: Option<String>,
+    pub due_date: Option<String>,
+    pub project_id: i32,
+    pub key: String,
+    pub milestone_id: Option<i32>,
+    pub version: i32,
+    pub deleted_at: Option<String>,
+    pub deleted_by: Option<i32>,
+    pub original_estimate: Option<i32>,
+    pub remaining_estimate: Option<i32>,
+    pub story_points: Option<i32>,
+    pub reporter_id: Option<i32>,
 }
 
 #[derive(Serialize, Deserialize, Debug, FromSqlRow, Clone)]
@@ -24,47 +39,498 @@ pub struct Ticket {
     pub body: String,
     pub created: String,
     pub last_modified: String,
-    pub labels: Vec<Label>,
+    pub labels: Vec<i32>,
+    /// First of the assignees, for clients that only know a single assignee.
     pub assigned_user: Option<i32>,
-    pub status: Status,
+    pub assignees: Vec<i32>,
+    pub watchers: Vec<i32>,
+    pub status: String,
+    pub priority: Priority,
+    pub severity: Severity,
+    pub start_date: Option<String>,
+    pub due_date: Option<String>,
+    pub project_id: i32,
+    pub key: String,
+    pub milestone_id: Option<i32>,
+    /// Increased with every change, clients send it back in `If-Match` to not overwrite changes of others.
+    pub version: i32,
+    /// Set while the ticket is in the trash.
+    pub deleted_at: Option<String>,
+    pub deleted_by: Option<i32>,
+    /// Estimates are in minutes, just like logged work.
+    pub original_estimate: Option<i32>,
+    pub remaining_estimate: Option<i32>,
+    pub story_points: Option<i32>,
+    /// User who filed the ticket, unknown for tickets filed before it was recorded.
+    pub reporter_id: Option<i32>,
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
-pub enum Label {
-    Feature,
-    Bug,
-    WontFix,
-    Done,
-    InProgress,
+/// Declared from lowest to highest, so comparing priorities compares their rank.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Priority {
+    Lowest = 1,
+    Low = 2,
+    #[default]
+    Medium = 3,
+    High = 4,
+    Highest = 5,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Copy)]
-pub enum Status {
-    Open,
-    Closed,
+impl Priority {
+    pub fn rank(&self) -> i32 {
+        *self as i32
+    }
+
+    pub fn from_rank(rank: i32) -> Option<Self> {
+        match rank {
+            1 => Some(Priority::Lowest),
+            2 => Some(Priority::Low),
+            3 => Some(Priority::Medium),
+            4 => Some(Priority::High),
+            5 => Some(Priority::Highest),
+            _ => None,
+        }
+    }
 }
 
-impl FromStr for Status {
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum Severity {
+    Trivial,
+    Minor,
+    #[default]
+    Normal,
+    Major,
+    Critical,
+}
+
+impl FromStr for Severity {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "Open" => Ok(Status::Open),
-            "Closed" => Ok(Status::Closed),
+            "Trivial" => Ok(Severity::Trivial),
+            "Minor" => Ok(Severity::Minor),
+            "Normal" => Ok(Severity::Normal),
+            "Major" => Ok(Severity::Major),
+            "Critical" => Ok(Severity::Critical),
             _ => Err(()),
         }
     }
 }
 
-impl ToString for Status {
-    fn to_string(&self) -> String {
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Trivial => write!(f, "Trivial"),
+            Severity::Minor => write!(f, "Minor"),
+            Severity::Normal => write!(f, "Normal"),
+            Severity::Major => write!(f, "Major"),
+            Severity::Critical => write!(f, "Critical"),
+        }
+    }
+}
+
+/// A ticket together with everything that is only loaded when a single ticket is requested.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TicketDetails {
+    #[serde(flatten)]
+    pub ticket: Ticket,
+    pub links: Vec<LinkedTicket>,
+}
+
+/// Types of links between tickets, each type has an inverse that describes the link
+/// from the other ticket's point of view.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum LinkType {
+    Blocks,
+    BlockedBy,
+    DuplicateOf,
+    DuplicatedBy,
+    RelatesTo,
+    ParentOf,
+    SubtaskOf,
+}
+
+impl LinkType {
+    pub fn inverse(&self) -> Self {
+        match self {
+            LinkType::Blocks => LinkType::BlockedBy,
+            LinkType::BlockedBy => LinkType::Blocks,
+            LinkType::DuplicateOf => LinkType::DuplicatedBy,
+            LinkType::DuplicatedBy => LinkType::DuplicateOf,
+            LinkType::RelatesTo => LinkType::RelatesTo,
+            LinkType::ParentOf => LinkType::SubtaskOf,
+            LinkType::SubtaskOf => LinkType::ParentOf,
+        }
+    }
+
+    /// Only one direction of every type is stored, so a link can't exist twice in different shapes.
+    pub fn is_stored(&self) -> bool {
+        matches!(
+            self,
+            LinkType::Blocks | LinkType::DuplicateOf | LinkType::RelatesTo | LinkType::ParentOf
+        )
+    }
+
+    /// Links of these types must never lead back to the ticket they start from.
+    pub fn forbids_cycles(&self) -> bool {
+        matches!(self, LinkType::Blocks | LinkType::ParentOf)
+    }
+}
+
+impl FromStr for LinkType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Blocks" => Ok(LinkType::Blocks),
+            "BlockedBy" => Ok(LinkType::BlockedBy),
+            "DuplicateOf" => Ok(LinkType::DuplicateOf),
+            "DuplicatedBy" => Ok(LinkType::DuplicatedBy),
+            "RelatesTo" => Ok(LinkType::RelatesTo),
+            "ParentOf" => Ok(LinkType::ParentOf),
+            "SubtaskOf" => Ok(LinkType::SubtaskOf),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for LinkType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Serialize, Deserialize, Queryable, Debug)]
+#[diesel(table_name = crate::schema::ticket_links)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TicketLink {
+    pub id: i32,
+    pub source_ticket_id: i32,
+    pub target_ticket_id: i32,
+    pub link_type: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::ticket_links)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewTicketLink {
+    pub source_ticket_id: i32,
+    pub target_ticket_id: i32,
+    pub link_type: String,
+}
+
+/// A link as seen from one of the two tickets it connects.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct LinkedTicket {
+    pub id: i32,
+    pub ticket_id: i32,
+    pub link_type: LinkType,
+}
+
+impl TicketLink {
+    pub fn seen_from(&self, ticket_id: i32) -> LinkedTicket {
+        // links are only stored in one direction, so the other ticket sees the inverse type
+        let link_type = LinkType::from_str(&self.link_type).unwrap_or(LinkType::RelatesTo);
+
+        if self.source_ticket_id == ticket_id {
+            LinkedTicket {
+                id: self.id,
+                ticket_id: self.target_ticket_id,
+                link_type,
+            }
+        } else {
+            LinkedTicket {
+                id: self.id,
+                ticket_id: self.source_ticket_id,
+                link_type: link_type.inverse(),
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Queryable, Debug)]
+#[diesel(table_name = crate::schema::projects)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Project {
+    pub id: i32,
+    pub key: String,
+    pub name: String,
+    pub ticket_counter: i32,
+}
+
+impl Project {
+    /// Keys prefix the numbers of the project's tickets, like WEB in WEB-42.
+    pub fn is_valid_key(key: &str) -> bool {
+        let mut chars = key.chars();
+
+        (2..=10).contains(&key.len())
+            && chars.next().is_some_and(|c| c.is_ascii_uppercase())
+            && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+    }
+
+    pub fn ticket_key(&self, number: i32) -> String {
+        format!("{}-{}", self.key, number)
+    }
+}
+
+#[derive(Serialize, Deserialize, Insertable, Debug)]
+#[diesel(table_name = crate::schema::projects)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewProject {
+    pub key: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum MilestoneState {
+    #[default]
+    Planned,
+    Active,
+    Completed,
+}
+
+impl fmt::Display for MilestoneState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Status::Open => String::from("Open"),
-            Status::Closed => String::from("Closed"),
+            MilestoneState::Planned => write!(f, "Planned"),
+            MilestoneState::Active => write!(f, "Active"),
+            MilestoneState::Completed => write!(f, "Completed"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Queryable, Debug)]
+#[diesel(table_name = crate::schema::milestones)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Milestone {
+    pub id: i32,
+    pub name: String,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub state: String,
+}
+
+#[derive(Insertable, AsChangeset, Debug)]
+#[diesel(table_name = crate::schema::milestones)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+// without this, dates that are left out on update would be kept instead of removed
+#[diesel(treat_none_as_null = true)]
+pub struct NewMilestone {
+    pub name: String,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub state: String,
+}
+
+impl From<MilestonePayload> for NewMilestone {
+    fn from(milestone: MilestonePayload) -> Self {
+        NewMilestone {
+            name: milestone.name,
+            start_date: milestone.start_date,
+            end_date: milestone.end_date,
+            state: milestone.state.to_string(),
         }
     }
 }
 
+#[derive(Queryable, Debug)]
+#[diesel(table_name = crate::schema::ticket_templates)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct SqliteTicketTemplate {
+    pub id: i32,
+    pub name: String,
+    pub title_pattern: String,
+    pub body: String,
+    pub labels: String,
+    pub status: Option<String>,
+    pub assigned_user: Option<i32>,
+}
+
+/// Defaults for tickets that are filed the same way again and again.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TicketTemplate {
+    pub id: i32,
+    pub name: String,
+    /// Title of created tickets, `{title}` is replaced with the submitted title.
+    pub title_pattern: String,
+    pub body: String,
+    pub labels: Vec<i32>,
+    pub status: Option<String>,
+    pub assigned_user: Option<i32>,
+}
+
+impl From<SqliteTicketTemplate> for TicketTemplate {
+    fn from(template: SqliteTicketTemplate) -> Self {
+        TicketTemplate {
+            id: template.id,
+            name: template.name,
+            title_pattern: template.title_pattern,
+            body: template.body,
+            // a malformed row leaves the template without default labels instead of failing
+            labels: serde_json::from_str(&template.labels).unwrap_or_default(),
+            status: template.status,
+            assigned_user: template.assigned_user,
+        }
+    }
+}
+
+#[derive(Insertable, AsChangeset, Debug)]
+#[diesel(table_name = crate::schema::ticket_templates)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+#[diesel(treat_none_as_null = true)]
+pub struct NewTicketTemplate {
+    pub name: String,
+    pub title_pattern: String,
+    pub body: String,
+    pub labels: String,
+    pub status: Option<String>,
+    pub assigned_user: Option<i32>,
+}
+
+impl From<TemplatePayload> for NewTicketTemplate {
+    fn from(template: TemplatePayload) -> Self {
+        NewTicketTemplate {
+            name: template.name,
+            title_pattern: template.title_pattern,
+            body: template.body,
+            labels: serde_json::to_string(&template.labels).unwrap(),
+            status: template.status,
+            assigned_user: template.assigned_user,
+        }
+    }
+}
+
+#[derive(Queryable, Debug)]
+#[diesel(table_name = crate::schema::saved_filters)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct SqliteSavedFilter {
+    pub id: i32,
+    pub owner_id: i32,
+    pub name: String,
+    pub filter: String,
+    pub shared: bool,
+    pub created: String,
+    pub last_modified: String,
+}
+
+/// A filter stored under a name, so it doesn't have to be entered again.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SavedFilter {
+    pub id: i32,
+    pub owner_id: i32,
+    pub name: String,
+    /// Fields of a `FilterPayload`, user ids may be `"me"` to stand for whoever runs the filter.
+    pub filter: Value,
+    /// Shared filters can be seen and run by all users, but only changed by their owner.
+    pub shared: bool,
+    pub created: String,
+    pub last_modified: String,
+}
+
+impl TryFrom<SqliteSavedFilter> for SavedFilter {
+    type Error = serde_json::Error;
+
+    fn try_from(saved_filter: SqliteSavedFilter) -> Result<Self, Self::Error> {
+        Ok(SavedFilter {
+            id: saved_filter.id,
+            owner_id: saved_filter.owner_id,
+            name: saved_filter.name,
+            filter: serde_json::from_str(&saved_filter.filter)?,
+            shared: saved_filter.shared,
+            created: saved_filter.created,
+            last_modified: saved_filter.last_modified,
+        })
+    }
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::saved_filters)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewSavedFilter {
+    pub owner_id: i32,
+    pub name: String,
+    pub filter: String,
+    pub shared: bool,
+    pub created: String,
+    pub last_modified: String,
+}
+
+/// Outcome of a bulk operation for a single ticket.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BulkResult {
+    pub ticket_id: i32,
+    pub ticket: Option<Ticket>,
+    pub error: Option<String>,
+}
+
+/// Progress of a milestone, computed from whether the status of each of its tickets is closed.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MilestoneSummary {
+    #[serde(flatten)]
+    pub milestone: Milestone,
+    pub total_tickets: i64,
+    pub open_tickets: i64,
+    pub closed_tickets: i64,
+    /// Percentage of closed tickets, rounded down.
+    pub progress: i64,
+}
+
+#[derive(Serialize, Deserialize, Queryable, Debug, Clone)]
+#[diesel(table_name = crate::schema::labels)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Label {
+    pub id: i32,
+    pub name: String,
+    pub color: String,
+    pub description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Insertable, AsChangeset, Debug)]
+#[diesel(table_name = crate::schema::labels)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+// without this, a description that is left out on update would be kept instead of removed
+#[diesel(treat_none_as_null = true)]
+pub struct NewLabel {
+    pub name: String,
+    pub color: String,
+    pub description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Queryable, Debug, Clone)]
+#[diesel(table_name = crate::schema::statuses)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Status {
+    pub id: i32,
+    pub name: String,
+    pub is_closed: bool,
+}
+
+#[derive(Serialize, Deserialize, Insertable, Debug)]
+#[diesel(table_name = crate::schema::statuses)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewStatus {
+    pub name: String,
+    pub is_closed: bool,
+}
+
+#[derive(Serialize, Deserialize, Queryable, Debug)]
+#[diesel(table_name = crate::schema::status_transitions)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct StatusTransition {
+    pub id: i32,
+    pub from_status: String,
+    pub to_status: String,
+}
+
+#[derive(Serialize, Deserialize, Insertable, Debug)]
+#[diesel(table_name = crate::schema::status_transitions)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewStatusTransition {
+    pub from_status: String,
+    pub to_status: String,
+}
+
 #[derive(Insertable, Debug)]
 #[diesel(table_name = crate::schema::tickets)]
 #[diesel(check_for_backend(diesel::sqlite::Sqlite))]
@@ -73,24 +539,83 @@ pub struct NewTicket {
     pub body: String,
     pub created: String,
     pub last_modified: String,
-    pub labels: String,
-    pub assigned_user: Option<i32>,
     pub status: String,
+    pub priority: i32,
+    pub severity: String,
+    pub start_date: Option<String>,
+    pub due_date: Option<String>,
+    pub project_id: i32,
+    pub key: String,
+    pub milestone_id: Option<i32>,
+    pub original_estimate: Option<i32>,
+    pub remaining_estimate: Option<i32>,
+    pub story_points: Option<i32>,
+    pub reporter_id: Option<i32>,
 }
 
 impl SqliteTicket {
-    pub fn to_ticket(&self) -> Ticket {
+    /// Labels, assignees and watchers are stored in their own tables, so they have to be loaded separately.
+    pub fn to_ticket(&self, labels: Vec<i32>, assignees: Vec<i32>, watchers: Vec<i32>) -> Ticket {
         Ticket {
             id: self.id,
             title: self.title.clone(),
             body: self.body.clone(),
             created: self.created.clone(),
             last_modified: self.last_modified.clone(),
-            labels: serde_json::from_str(&self.labels).unwrap(),
-            assigned_user: self.assigned_user,
-            status: Status::from_str(&self.status).unwrap(),
+            labels,
+            assigned_user: assignees.first().copied(),
+            assignees,
+            watchers,
+            status: self.status.clone(),
+            priority: Priority::from_rank(self.priority).unwrap_or_default(),
+            severity: Severity::from_str(&self.severity).unwrap_or_default(),
+            start_date: self.start_date.clone(),
+            due_date: self.due_date.clone(),
+            project_id: self.project_id,
+            key: self.key.clone(),
+            milestone_id: self.milestone_id,
+            version: self.version,
+            deleted_at: self.deleted_at.clone(),
+            deleted_by: self.deleted_by,
+            original_estimate: self.original_estimate,
+            remaining_estimate: self.remaining_estimate,
+            story_points: self.story_points,
+            reporter_id: self.reporter_id,
         }
     }
+
+    /// Fields whose changes end up in the ticket history, as they are stored in the database.
+    pub fn tracked_fields(&self) -> Vec<(&'static str, Option<String>)> {
+        vec![
+            ("title", Some(self.title.clone())),
+            ("body", Some(self.body.clone())),
+            ("status", Some(self.status.clone())),
+            (
+                "priority",
+                Priority::from_rank(self.priority).map(|priority| format!("{:?}", priority)),
+            ),
+            ("severity", Some(self.severity.clone())),
+            ("start_date", self.start_date.clone()),
+            ("due_date", self.due_date.clone()),
+            (
+                "milestone_id",
+                self.milestone_id.map(|milestone| milestone.to_string()),
+            ),
+            (
+                "original_estimate",
+                self.original_estimate.map(|minutes| minutes.to_string()),
+            ),
+            (
+                "remaining_estimate",
+                self.remaining_estimate.map(|minutes| minutes.to_string()),
+            ),
+            (
+                "story_points",
+                self.story_points.map(|points| points.to_string()),
+            ),
+            ("deleted_at", self.deleted_at.clone()),
+        ]
+    }
 }
 
 #[derive(Serialize, Deserialize, Queryable, Debug)]
@@ -117,7 +642,7 @@ pub struct NewUser {
     pub password: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct TokenClaims {
     pub id: i32,
     pub exp: u64,
@@ -135,3 +660,205 @@ pub struct DatabaseSession {
 pub struct NewSession {
     pub token: String,
 }
+
+#[derive(Serialize, Deserialize, Queryable, Debug)]
+#[diesel(table_name = crate::schema::ticket_comments)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Comment {
+    pub id: i32,
+    pub ticket_id: i32,
+    pub author_id: i32,
+    pub parent_comment_id: Option<i32>,
+    pub body: String,
+    pub created: String,
+    pub last_modified: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::ticket_comments)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewComment {
+    pub ticket_id: i32,
+    pub author_id: i32,
+    pub parent_comment_id: Option<i32>,
+    pub body: String,
+    pub created: String,
+    pub last_modified: String,
+}
+
+/// Why a ticket query couldn't be understood. `start` and `end` are the character offsets of the culprit in the query.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct QueryError {
+    pub message: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A ticket matching a full-text search, as it comes out of the search index.
+#[derive(QueryableByName, Debug)]
+pub struct SearchHit {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub ticket_id: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub title: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub snippet: String,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub score: f64,
+}
+
+/// Matches in `title` and `snippet` are wrapped in `<mark>` tags, the best match comes first.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SearchResult {
+    pub ticket: Ticket,
+    /// The whole title with its matches highlighted.
+    pub title: String,
+    /// The part of the body around the matches, with the matches highlighted.
+    pub snippet: String,
+    /// Higher is more relevant.
+    pub score: f64,
+}
+
+/// A user was mentioned as `@display_name` in the title or body of a ticket.
+#[derive(Serialize, Deserialize, Queryable, Debug)]
+#[diesel(table_name = crate::schema::mentions)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Mention {
+    pub id: i32,
+    pub ticket_id: i32,
+    /// The mentioned user.
+    pub user_id: i32,
+    /// The user whose change added the mention.
+    pub actor_id: i32,
+    pub created: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::mentions)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewMention {
+    pub ticket_id: i32,
+    pub user_id: i32,
+    pub actor_id: i32,
+    pub created: String,
+}
+
+#[derive(Serialize, Deserialize, Queryable, Debug)]
+#[diesel(table_name = crate::schema::worklogs)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Worklog {
+    pub id: i32,
+    pub ticket_id: i32,
+    pub user_id: i32,
+    pub minutes: i32,
+    /// Day the work was done on, formatted YYYY-MM-DD.
+    pub date: String,
+    pub note: Option<String>,
+    pub created: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::worklogs)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewWorklog {
+    pub ticket_id: i32,
+    pub user_id: i32,
+    pub minutes: i32,
+    pub date: String,
+    pub note: Option<String>,
+    pub created: String,
+}
+
+/// Logged work within a date range, in total and per ticket and user.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WorklogSummary {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub total_minutes: i64,
+    pub tickets: Vec<TicketWorklogTotal>,
+    pub users: Vec<UserWorklogTotal>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct TicketWorklogTotal {
+    pub ticket_id: i32,
+    pub minutes: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct UserWorklogTotal {
+    pub user_id: i32,
+    pub minutes: i64,
+}
+
+/// Burndown and burnup of story points over a date range, one entry per day.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Burndown {
+    pub from: String,
+    pub to: String,
+    pub milestone_id: Option<i32>,
+    pub days: Vec<BurndownDay>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct BurndownDay {
+    pub date: String,
+    /// Points of all tickets in scope, the burnup's upper line.
+    pub total_points: i64,
+    pub completed_points: i64,
+    pub remaining_points: i64,
+    /// Remaining points if the work was done at a steady pace.
+    pub ideal_points: f64,
+}
+
+#[derive(Serialize, Deserialize, Queryable, Debug)]
+#[diesel(table_name = crate::schema::ticket_history)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct HistoryEntry {
+    pub id: i32,
+    pub ticket_id: i32,
+    pub actor_id: i32,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub changed: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::ticket_history)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewHistoryEntry {
+    pub ticket_id: i32,
+    pub actor_id: i32,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub changed: String,
+}
+
+#[derive(Serialize, Deserialize, Queryable, Debug)]
+#[diesel(table_name = crate::schema::attachments)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Attachment {
+    pub id: i32,
+    pub ticket_id: i32,
+    pub uploader_id: i32,
+    pub file_name: String,
+    pub mime_type: String,
+    pub size: i32,
+    pub checksum: String,
+    pub created: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::attachments)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewAttachment {
+    pub ticket_id: i32,
+    pub uploader_id: i32,
+    pub file_name: String,
+    pub mime_type: String,
+    pub size: i32,
+    pub checksum: String,
+    pub created: String,
+}
@@ -1,21 +1,243 @@
-use crate::models::{Label, Status};
+use crate::models::{LinkType, MilestoneState, Priority, Severity, Ticket};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TicketPayload {
     pub title: String,
     pub body: String,
-    pub labels: Vec<Label>,
+    pub labels: Vec<i32>,
+    /// Kept for clients that only know a single assignee, `assignees` takes precedence.
     pub assigned_user: Option<i32>,
-    pub status: Status,
+    /// Left out together with `assigned_user`, the assignees of an existing ticket stay as they are.
+    pub assignees: Option<Vec<i32>>,
+    /// Left out, the watchers of an existing ticket stay as they are.
+    pub watchers: Option<Vec<i32>>,
+    pub status: String,
+    #[serde(default)]
+    pub priority: Priority,
+    #[serde(default)]
+    pub severity: Severity,
+    pub start_date: Option<String>,
+    pub due_date: Option<String>,
+    pub milestone_id: Option<i32>,
+    /// In minutes.
+    pub original_estimate: Option<i32>,
+    pub remaining_estimate: Option<i32>,
+    pub story_points: Option<i32>,
 }
 
-#[derive(Serialize, Deserialize)]
+impl TicketPayload {
+    /// The payload that would leave `ticket` as it is when sent as an edit.
+    pub fn from_ticket(ticket: &Ticket) -> Self {
+        TicketPayload {
+            title: ticket.title.clone(),
+            body: ticket.body.clone(),
+            labels: ticket.labels.clone(),
+            assigned_user: None,
+            assignees: Some(ticket.assignees.clone()),
+            watchers: Some(ticket.watchers.clone()),
+            status: ticket.status.clone(),
+            priority: ticket.priority,
+            severity: ticket.severity,
+            start_date: ticket.start_date.clone(),
+            due_date: ticket.due_date.clone(),
+            milestone_id: ticket.milestone_id,
+            original_estimate: ticket.original_estimate,
+            remaining_estimate: ticket.remaining_estimate,
+            story_points: ticket.story_points,
+        }
+    }
+
+    pub fn assignee_ids(&self) -> Option<Vec<i32>> {
+        match (&self.assignees, self.assigned_user) {
+            (Some(assignees), _) => Some(assignees.clone()),
+            (None, Some(assigned_user)) => Some(vec![assigned_user]),
+            (None, None) => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
 pub struct FilterPayload {
-    pub labels: Option<Vec<Label>>,
+    pub labels: Option<Vec<i32>>,
     pub assigned_user: Option<i32>,
+    pub assignees: Option<Vec<i32>>,
+    pub assignee_match: Option<AssigneeMatch>,
     pub title: Option<String>,
-    pub status: Option<Status>,
+    pub status: Option<String>,
+    pub priority: Option<Priority>,
+    pub severity: Option<Severity>,
+    pub overdue: Option<bool>,
+    pub due_before: Option<String>,
+    pub due_after: Option<String>,
+    pub milestone_id: Option<i32>,
+    pub reporter_id: Option<i32>,
+    /// Words to search for in titles and bodies, just like in the search endpoint.
+    pub text: Option<String>,
+    pub exclude_labels: Option<Vec<i32>>,
+    pub exclude_statuses: Option<Vec<String>>,
+    pub exclude_assignees: Option<Vec<i32>>,
+    pub created_before: Option<String>,
+    pub created_after: Option<String>,
+    pub updated_before: Option<String>,
+    pub updated_after: Option<String>,
+    pub sort: Option<TicketSort>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedFilterPayload {
+    pub name: String,
+    /// Same fields as `FilterPayload`, user ids may be `"me"`.
+    pub filter: Value,
+    #[serde(default)]
+    pub shared: bool,
+}
+
+/// Changes one request makes to several tickets, either those in `ids` or those matching `filter`.
+#[derive(Serialize, Deserialize)]
+pub struct BulkPayload {
+    pub ids: Option<Vec<i32>>,
+    pub filter: Option<FilterPayload>,
+    pub operations: BulkOperations,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct BulkOperations {
+    #[serde(default)]
+    pub add_labels: Vec<i32>,
+    #[serde(default)]
+    pub remove_labels: Vec<i32>,
+    pub status: Option<String>,
+    /// Replaces the assignees, an empty list unassigns everyone.
+    pub assignees: Option<Vec<i32>>,
+}
+
+impl BulkOperations {
+    pub fn is_empty(&self) -> bool {
+        self.add_labels.is_empty()
+            && self.remove_labels.is_empty()
+            && self.status.is_none()
+            && self.assignees.is_none()
+    }
+
+    pub fn apply(&self, ticket: &mut TicketPayload) {
+        for label in &self.add_labels {
+            if !ticket.labels.contains(label) {
+                ticket.labels.push(*label);
+            }
+        }
+        ticket
+            .labels
+            .retain(|label| !self.remove_labels.contains(label));
+        if let Some(status) = &self.status {
+            ticket.status = status.clone();
+        }
+        if let Some(assignees) = &self.assignees {
+            ticket.assignees = Some(assignees.clone());
+        }
+    }
+}
+
+/// Whether tickets must be assigned to any or to all of the assignees that are filtered for.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum AssigneeMatch {
+    #[default]
+    Any,
+    All,
+}
+
+/// Order of ticket lists, a leading "-" sorts descending.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum TicketSort {
+    #[serde(rename = "priority")]
+    Priority,
+    #[serde(rename = "-priority")]
+    PriorityDescending,
+    #[serde(rename = "created")]
+    Created,
+    #[serde(rename = "-created")]
+    CreatedDescending,
+    #[serde(rename = "updated")]
+    Updated,
+    #[serde(rename = "-updated")]
+    UpdatedDescending,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TicketListQuery {
+    pub sort: Option<TicketSort>,
+    /// Filter in the query language, like `status:Open label:Bug`.
+    pub q: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CommentPayload {
+    pub body: String,
+    pub parent_comment_id: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WorklogPayload {
+    pub minutes: i32,
+    /// Defaults to today.
+    pub date: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+    pub limit: Option<i64>,
+}
+
+/// Both ends of the date range are inclusive.
+#[derive(Serialize, Deserialize)]
+pub struct BurndownQuery {
+    pub from: String,
+    pub to: String,
+    pub milestone_id: Option<i32>,
+}
+
+/// Both ends of the date range are inclusive.
+#[derive(Serialize, Deserialize)]
+pub struct WorklogQuery {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub ticket_id: Option<i32>,
+    pub user_id: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MilestonePayload {
+    pub name: String,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    #[serde(default)]
+    pub state: MilestoneState,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TemplatePayload {
+    pub name: String,
+    #[serde(default = "default_title_pattern")]
+    pub title_pattern: String,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub labels: Vec<i32>,
+    pub status: Option<String>,
+    pub assigned_user: Option<i32>,
+}
+
+fn default_title_pattern() -> String {
+    "{title}".to_string()
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LinkPayload {
+    pub ticket_id: i32,
+    pub link_type: LinkType,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
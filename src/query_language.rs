@@ -0,0 +1,435 @@
+use crate::dates::parse_date;
+use crate::models::{DisplayUser, Label, Priority, QueryError, Severity, Status};
+use crate::payloads::{AssigneeMatch, FilterPayload, TicketSort};
+use crate::status_messages::{
+    ERROR_INVALID_DATE, ERROR_QUERY_AMBIGUOUS_USER, ERROR_QUERY_CANNOT_NEGATE,
+    ERROR_QUERY_INVALID_OPERATOR, ERROR_QUERY_MISSING_QUOTE, ERROR_QUERY_MISSING_VALUE,
+    ERROR_QUERY_REPEATED_FIELD, ERROR_QUERY_UNKNOWN_FIELD, ERROR_QUERY_UNKNOWN_VALUE,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/*
+ * A query is a list of terms separated by whitespace, all of which have to match:
+ *
+ *   status:Open label:Bug assignee:me -label:WontFix "login page" created>2026-01-01 sort:-updated
+ *
+ * Terms are either `field:value`, comparisons of dates like `due<=2026-10-31`, or words and "quoted phrases"
+ * that are searched for in titles and bodies. A leading `-` negates a term, values with spaces can be quoted.
+ */
+
+/// Names, ids and the current user that values in a query are resolved against.
+pub struct QueryContext<'a> {
+    pub labels: &'a [Label],
+    pub statuses: &'a [Status],
+    pub users: &'a [DisplayUser],
+    /// The user `me` stands for.
+    pub me: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Is,
+    Before,
+    BeforeOrOn,
+    After,
+    AfterOrOn,
+}
+
+/// Character offsets, `end` is exclusive.
+#[derive(Debug, Clone, Copy)]
+struct Span {
+    start: usize,
+    end: usize,
+}
+
+impl Span {
+    fn error(self, message: String) -> QueryError {
+        QueryError {
+            message,
+            start: self.start,
+            end: self.end,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FieldTerm {
+    name: String,
+    name_span: Span,
+    operator: Operator,
+    operator_span: Span,
+}
+
+#[derive(Debug)]
+struct Term {
+    negated: bool,
+    /// `None` for words and phrases.
+    field: Option<FieldTerm>,
+    value: String,
+    /// The term as it was written, words and phrases are handed to the full-text search like that.
+    source: String,
+    span: Span,
+    value_span: Span,
+}
+
+impl Term {
+    fn field_name(&self) -> &str {
+        self.field.as_ref().map_or("", |field| field.name.as_str())
+    }
+
+    fn only_is(&self) -> Result<(), QueryError> {
+        match &self.field {
+            Some(field) if field.operator != Operator::Is => Err(field
+                .operator_span
+                .error(format!("{} {}", ERROR_QUERY_INVALID_OPERATOR, field.name))),
+            _ => Ok(()),
+        }
+    }
+
+    fn not_negated(&self) -> Result<(), QueryError> {
+        if self.negated {
+            return Err(self.span.error(format!(
+                "{} {}",
+                ERROR_QUERY_CANNOT_NEGATE,
+                self.field_name()
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn unknown_value(&self) -> QueryError {
+        self.value_span.error(format!(
+            "{} {}, got {}",
+            ERROR_QUERY_UNKNOWN_VALUE,
+            self.field_name(),
+            self.value
+        ))
+    }
+
+    /// Fields that only take one value are filled only once, a second value is rather a typo than intended.
+    fn set_once<T>(&self, slot: &mut Option<T>, value: T) -> Result<(), QueryError> {
+        if slot.is_some() {
+            return Err(self.span.error(format!(
+                "{} {}",
+                ERROR_QUERY_REPEATED_FIELD,
+                self.field_name()
+            )));
+        }
+
+        *slot = Some(value);
+        Ok(())
+    }
+}
+
+/// Turns a query into the filter it describes, or tells exactly which part of the query is wrong.
+pub fn parse_query(query: &str, context: &QueryContext) -> Result<FilterPayload, QueryError> {
+    let mut filter = FilterPayload::default();
+    let mut text = vec![];
+
+    for term in tokenize(query)? {
+        let Some(field) = &term.field else {
+            text.push(term.source);
+            continue;
+        };
+
+        match field.name.as_str() {
+            "status" => {
+                term.only_is()?;
+                let status = context
+                    .statuses
+                    .iter()
+                    .find(|status| status.name.eq_ignore_ascii_case(&term.value))
+                    .ok_or_else(|| term.unknown_value())?;
+                if term.negated {
+                    push(&mut filter.exclude_statuses, status.name.clone());
+                } else {
+                    term.set_once(&mut filter.status, status.name.clone())?;
+                }
+            }
+            "label" => {
+                term.only_is()?;
+                let label = context
+                    .labels
+                    .iter()
+                    .find(|label| label.name.eq_ignore_ascii_case(&term.value))
+                    .ok_or_else(|| term.unknown_value())?;
+                if term.negated {
+                    push(&mut filter.exclude_labels, label.id);
+                } else {
+                    push(&mut filter.labels, label.id);
+                }
+            }
+            "assignee" => {
+                term.only_is()?;
+                let assignee = resolve_user(&term, context)?;
+                if term.negated {
+                    push(&mut filter.exclude_assignees, assignee);
+                } else {
+                    push(&mut filter.assignees, assignee);
+                    filter.assignee_match = Some(AssigneeMatch::All);
+                }
+            }
+            "reporter" => {
+                term.only_is()?;
+                term.not_negated()?;
+                let reporter = resolve_user(&term, context)?;
+                term.set_once(&mut filter.reporter_id, reporter)?;
+            }
+            "priority" => {
+                term.only_is()?;
+                term.not_negated()?;
+                let ticket_priority = parse_variant::<Priority>(&term)?;
+                term.set_once(&mut filter.priority, ticket_priority)?;
+            }
+            "severity" => {
+                term.only_is()?;
+                term.not_negated()?;
+                let ticket_severity = parse_variant::<Severity>(&term)?;
+                term.set_once(&mut filter.severity, ticket_severity)?;
+            }
+            "milestone" => {
+                term.only_is()?;
+                term.not_negated()?;
+                let milestone = term.value.parse().map_err(|_| term.unknown_value())?;
+                term.set_once(&mut filter.milestone_id, milestone)?;
+            }
+            "is" => {
+                term.only_is()?;
+                if !term.value.eq_ignore_ascii_case("overdue") {
+                    return Err(term.unknown_value());
+                }
+                term.set_once(&mut filter.overdue, !term.negated)?;
+            }
+            "due" => {
+                let (before, after) = (&mut filter.due_before, &mut filter.due_after);
+                set_date_bounds(&term, field.operator, before, after)?;
+            }
+            "created" => {
+                let (before, after) = (&mut filter.created_before, &mut filter.created_after);
+                set_date_bounds(&term, field.operator, before, after)?;
+            }
+            "updated" => {
+                let (before, after) = (&mut filter.updated_before, &mut filter.updated_after);
+                set_date_bounds(&term, field.operator, before, after)?;
+            }
+            "sort" => {
+                term.only_is()?;
+                term.not_negated()?;
+                let sort =
+                    serde_json::from_value::<TicketSort>(Value::String(term.value.to_lowercase()))
+                        .map_err(|_| term.unknown_value())?;
+                term.set_once(&mut filter.sort, sort)?;
+            }
+            _ => {
+                return Err(field
+                    .name_span
+                    .error(format!("{} {}", ERROR_QUERY_UNKNOWN_FIELD, field.name)))
+            }
+        }
+    }
+
+    if !text.is_empty() {
+        filter.text = Some(text.join(" "));
+    }
+
+    Ok(filter)
+}
+
+fn tokenize(query: &str) -> Result<Vec<Term>, QueryError> {
+    let chars: Vec<char> = query.chars().collect();
+    let mut terms = vec![];
+    let mut position = 0;
+
+    while position < chars.len() {
+        if chars[position].is_whitespace() {
+            position += 1;
+            continue;
+        }
+
+        let start = position;
+        let negated = chars[position] == '-';
+        if negated {
+            position += 1;
+        }
+
+        let name_length = chars[position..]
+            .iter()
+            .take_while(|c| c.is_alphanumeric() || **c == '_')
+            .count();
+        let name_end = position + name_length;
+
+        let (field, value_start) = match operator_at(&chars, name_end) {
+            Some((operator, operator_length)) if name_length > 0 => {
+                let field = FieldTerm {
+                    name: chars[position..name_end]
+                        .iter()
+                        .collect::<String>()
+                        .to_lowercase(),
+                    name_span: Span {
+                        start: position,
+                        end: name_end,
+                    },
+                    operator,
+                    operator_span: Span {
+                        start: name_end,
+                        end: name_end + operator_length,
+                    },
+                };
+                (Some(field), name_end + operator_length)
+            }
+            _ => (None, position),
+        };
+
+        let (value, value_span, end) = read_value(&chars, value_start)?;
+        let span = Span { start, end };
+        position = end;
+
+        if let Some(field) = &field {
+            if value.is_empty() {
+                return Err(span.error(format!("{} {}", ERROR_QUERY_MISSING_VALUE, field.name)));
+            }
+        } else if value.trim().is_empty() {
+            // a lone "-" or empty quotes don't search for anything
+            continue;
+        }
+
+        terms.push(Term {
+            negated,
+            field,
+            value,
+            source: chars[start..end].iter().collect(),
+            span,
+            value_span,
+        });
+    }
+
+    Ok(terms)
+}
+
+fn operator_at(chars: &[char], position: usize) -> Option<(Operator, usize)> {
+    match (chars.get(position), chars.get(position + 1)) {
+        (Some('<'), Some('=')) => Some((Operator::BeforeOrOn, 2)),
+        (Some('>'), Some('=')) => Some((Operator::AfterOrOn, 2)),
+        (Some('<'), _) => Some((Operator::Before, 1)),
+        (Some('>'), _) => Some((Operator::After, 1)),
+        (Some(':'), _) => Some((Operator::Is, 1)),
+        _ => None,
+    }
+}
+
+/// Reads a "quoted" value or a bare one up to the next whitespace.
+/// Returns the value, where it is and where the term it belongs to ends.
+fn read_value(chars: &[char], start: usize) -> Result<(String, Span, usize), QueryError> {
+    if chars.get(start) == Some(&'"') {
+        let value_start = start + 1;
+        let Some(length) = chars[value_start..].iter().position(|c| *c == '"') else {
+            return Err(Span {
+                start,
+                end: chars.len(),
+            }
+            .error(ERROR_QUERY_MISSING_QUOTE.to_string()));
+        };
+        let value_end = value_start + length;
+
+        return Ok((
+            chars[value_start..value_end].iter().collect(),
+            Span {
+                start: value_start,
+                end: value_end,
+            },
+            value_end + 1,
+        ));
+    }
+
+    let length = chars[start..]
+        .iter()
+        .take_while(|c| !c.is_whitespace())
+        .count();
+    let end = start + length;
+
+    Ok((chars[start..end].iter().collect(), Span { start, end }, end))
+}
+
+fn push<T>(values: &mut Option<Vec<T>>, value: T) {
+    values.get_or_insert_with(Vec::new).push(value);
+}
+
+/// Users are referred to as `me`, by id or by display name.
+fn resolve_user(term: &Term, context: &QueryContext) -> Result<i32, QueryError> {
+    if term.value.eq_ignore_ascii_case("me") {
+        return context.me.ok_or_else(|| term.unknown_value());
+    }
+
+    if let Ok(user_id) = term.value.parse::<i32>() {
+        return match context.users.iter().any(|user| user.id == user_id) {
+            true => Ok(user_id),
+            false => Err(term.unknown_value()),
+        };
+    }
+
+    let mut matching_users = context
+        .users
+        .iter()
+        .filter(|user| user.display_name.eq_ignore_ascii_case(&term.value));
+    match (matching_users.next(), matching_users.next()) {
+        (Some(user), None) => Ok(user.id),
+        (Some(_), Some(_)) => Err(term
+            .value_span
+            .error(format!("{} {}", ERROR_QUERY_AMBIGUOUS_USER, term.value))),
+        (None, _) => Err(term.unknown_value()),
+    }
+}
+
+/// Variants are written like they are serialized, apart from the case, so `high` is `High`.
+fn parse_variant<T: DeserializeOwned>(term: &Term) -> Result<T, QueryError> {
+    let mut chars = term.value.chars();
+    let capitalized = chars
+        .next()
+        .map(|first| {
+            first
+                .to_uppercase()
+                .chain(chars.flat_map(char::to_lowercase))
+        })
+        .map(String::from_iter)
+        .unwrap_or_default();
+
+    serde_json::from_value(Value::String(capitalized)).map_err(|_| term.unknown_value())
+}
+
+/// Date filters are exclusive, so `<=` and `>=` move the bound by a day and `:` matches the day itself.
+fn set_date_bounds(
+    term: &Term,
+    operator: Operator,
+    before: &mut Option<String>,
+    after: &mut Option<String>,
+) -> Result<(), QueryError> {
+    term.not_negated()?;
+    let invalid_date = || {
+        term.value_span
+            .error(format!("{} {}", ERROR_INVALID_DATE, term.value))
+    };
+    let date = parse_date(&term.value).ok_or_else(invalid_date)?;
+    let next_day = || {
+        date.next_day()
+            .map(|day| day.to_string())
+            .ok_or_else(invalid_date)
+    };
+    let previous_day = || {
+        date.previous_day()
+            .map(|day| day.to_string())
+            .ok_or_else(invalid_date)
+    };
+
+    match operator {
+        Operator::Is => {
+            term.set_once(before, next_day()?)?;
+            term.set_once(after, previous_day()?)
+        }
+        Operator::Before => term.set_once(before, date.to_string()),
+        Operator::BeforeOrOn => term.set_once(before, next_day()?),
+        Operator::After => term.set_once(after, date.to_string()),
+        Operator::AfterOrOn => term.set_once(after, previous_day()?),
+    }
+}
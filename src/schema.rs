@@ -1,5 +1,68 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    attachments (id) {
+        id -> Integer,
+        ticket_id -> Integer,
+        uploader_id -> Integer,
+        file_name -> Text,
+        mime_type -> Text,
+        size -> Integer,
+        checksum -> Text,
+        created -> Text,
+    }
+}
+
+diesel::table! {
+    labels (id) {
+        id -> Integer,
+        name -> Text,
+        color -> Text,
+        description -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    mentions (id) {
+        id -> Integer,
+        ticket_id -> Integer,
+        user_id -> Integer,
+        actor_id -> Integer,
+        created -> Text,
+    }
+}
+
+diesel::table! {
+    milestones (id) {
+        id -> Integer,
+        name -> Text,
+        start_date -> Nullable<Text>,
+        end_date -> Nullable<Text>,
+        state -> Text,
+    }
+}
+
+diesel::table! {
+    projects (id) {
+        id -> Integer,
+        key -> Text,
+        name -> Text,
+        ticket_counter -> Integer,
+    }
+}
+
+diesel::table! {
+    saved_filters (id) {
+        id -> Integer,
+        owner_id -> Integer,
+        name -> Text,
+        filter -> Text,
+        shared -> Bool,
+        created -> Text,
+        last_modified -> Text,
+    }
+}
+
 diesel::table! {
     sessions (id) {
         id -> Integer,
@@ -8,15 +71,109 @@ diesel::table! {
 }
 
 diesel::table! {
-    tickets (id) {
+    status_transitions (id) {
         id -> Integer,
-        title -> Text,
+        from_status -> Text,
+        to_status -> Text,
+    }
+}
+
+diesel::table! {
+    statuses (id) {
+        id -> Integer,
+        name -> Text,
+        is_closed -> Bool,
+    }
+}
+
+diesel::table! {
+    ticket_assignees (ticket_id, user_id) {
+        ticket_id -> Integer,
+        user_id -> Integer,
+    }
+}
+
+diesel::table! {
+    ticket_comments (id) {
+        id -> Integer,
+        ticket_id -> Integer,
+        author_id -> Integer,
+        parent_comment_id -> Nullable<Integer>,
         body -> Text,
         created -> Text,
         last_modified -> Text,
+    }
+}
+
+diesel::table! {
+    ticket_history (id) {
+        id -> Integer,
+        ticket_id -> Integer,
+        actor_id -> Integer,
+        field -> Text,
+        old_value -> Nullable<Text>,
+        new_value -> Nullable<Text>,
+        changed -> Text,
+    }
+}
+
+diesel::table! {
+    ticket_labels (ticket_id, label_id) {
+        ticket_id -> Integer,
+        label_id -> Integer,
+    }
+}
+
+diesel::table! {
+    ticket_links (id) {
+        id -> Integer,
+        source_ticket_id -> Integer,
+        target_ticket_id -> Integer,
+        link_type -> Text,
+    }
+}
+
+diesel::table! {
+    ticket_templates (id) {
+        id -> Integer,
+        name -> Text,
+        title_pattern -> Text,
+        body -> Text,
         labels -> Text,
+        status -> Nullable<Text>,
         assigned_user -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    ticket_watchers (ticket_id, user_id) {
+        ticket_id -> Integer,
+        user_id -> Integer,
+    }
+}
+
+diesel::table! {
+    tickets (id) {
+        id -> Integer,
+        title -> Text,
+        body -> Text,
+        created -> Text,
+        last_modified -> Text,
         status -> Text,
+        priority -> Integer,
+        severity -> Text,
+        start_date -> Nullable<Text>,
+        due_date -> Nullable<Text>,
+        project_id -> Integer,
+        key -> Text,
+        milestone_id -> Nullable<Integer>,
+        version -> Integer,
+        deleted_at -> Nullable<Text>,
+        deleted_by -> Nullable<Integer>,
+        original_estimate -> Nullable<Integer>,
+        remaining_estimate -> Nullable<Integer>,
+        story_points -> Nullable<Integer>,
+        reporter_id -> Nullable<Integer>,
     }
 }
 
@@ -29,4 +186,55 @@ diesel::table! {
     }
 }
 
-diesel::allow_tables_to_appear_in_same_query!(sessions, tickets, users,);
+diesel::table! {
+    worklogs (id) {
+        id -> Integer,
+        ticket_id -> Integer,
+        user_id -> Integer,
+        minutes -> Integer,
+        date -> Text,
+        note -> Nullable<Text>,
+        created -> Text,
+    }
+}
+
+diesel::joinable!(attachments -> tickets (ticket_id));
+diesel::joinable!(attachments -> users (uploader_id));
+diesel::joinable!(mentions -> tickets (ticket_id));
+diesel::joinable!(saved_filters -> users (owner_id));
+diesel::joinable!(ticket_assignees -> tickets (ticket_id));
+diesel::joinable!(ticket_assignees -> users (user_id));
+diesel::joinable!(ticket_comments -> tickets (ticket_id));
+diesel::joinable!(ticket_comments -> users (author_id));
+diesel::joinable!(ticket_history -> users (actor_id));
+diesel::joinable!(ticket_labels -> labels (label_id));
+diesel::joinable!(ticket_labels -> tickets (ticket_id));
+diesel::joinable!(ticket_templates -> users (assigned_user));
+diesel::joinable!(ticket_watchers -> tickets (ticket_id));
+diesel::joinable!(ticket_watchers -> users (user_id));
+diesel::joinable!(tickets -> milestones (milestone_id));
+diesel::joinable!(tickets -> projects (project_id));
+diesel::joinable!(worklogs -> tickets (ticket_id));
+diesel::joinable!(worklogs -> users (user_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    attachments,
+    labels,
+    mentions,
+    milestones,
+    projects,
+    saved_filters,
+    sessions,
+    status_transitions,
+    statuses,
+    ticket_assignees,
+    ticket_comments,
+    ticket_history,
+    ticket_labels,
+    ticket_links,
+    ticket_templates,
+    ticket_watchers,
+    tickets,
+    users,
+    worklogs,
+);
@@ -0,0 +1,68 @@
+/// What users type into a search box: words and "quoted phrases", those with a leading `-` must not appear.
+/// Terms are quoted for FTS5, so characters it treats as syntax, like `:` or `*`, are searched for literally.
+pub struct SearchTerms {
+    included: Vec<String>,
+    excluded: Vec<String>,
+}
+
+impl SearchTerms {
+    pub fn parse(text: &str) -> Self {
+        let mut terms = SearchTerms {
+            included: vec![],
+            excluded: vec![],
+        };
+        let mut chars = text.chars().peekable();
+
+        while let Some(&next) = chars.peek() {
+            if next.is_whitespace() {
+                chars.next();
+                continue;
+            }
+
+            let excluded = chars.next_if_eq(&'-').is_some();
+            let term: String = if chars.next_if_eq(&'"').is_some() {
+                chars.by_ref().take_while(|c| *c != '"').collect()
+            } else {
+                std::iter::from_fn(|| chars.next_if(|c| !c.is_whitespace())).collect()
+            };
+            if term.trim().is_empty() {
+                continue;
+            }
+
+            let quoted = format!("\"{}\"", term.replace('"', "\"\""));
+            if excluded {
+                terms.excluded.push(quoted);
+            } else {
+                terms.included.push(quoted);
+            }
+        }
+
+        terms
+    }
+
+    /// FTS5 query matching tickets that contain all included and none of the excluded terms.
+    /// Returns `None` if nothing is included, FTS5 can't search for the absence of terms alone.
+    pub fn match_expression(&self) -> Option<String> {
+        if self.included.is_empty() {
+            return None;
+        }
+
+        let mut expression = format!("({})", self.included.join(" "));
+        for term in &self.excluded {
+            expression.push_str(" NOT ");
+            expression.push_str(term);
+        }
+
+        Some(expression)
+    }
+
+    /// FTS5 query matching tickets that contain any of the excluded terms.
+    pub fn excluded_expression(&self) -> Option<String> {
+        (!self.excluded.is_empty()).then(|| self.excluded.join(" OR "))
+    }
+}
+
+/// Returns `None` if there is nothing to search for.
+pub fn match_expression(text: &str) -> Option<String> {
+    SearchTerms::parse(text).match_expression()
+}
@@ -12,6 +12,120 @@ pub const ERROR_INCORRECT_PASSWORD: &str = "Incorrect email or password";
 pub const ERROR_NO_USER_FOUND: &str = "No user found";
 pub const ERROR_USER_ALREADY_EXISTS: &str = "User with that email already exists";
 pub const ERROR_COULD_NOT_RETRIEVE_USERS: &str = "Could not retriever users";
+pub const ERROR_LABEL_NOT_FOUND: &str = "Could not find label with id";
+pub const ERROR_UNKNOWN_LABEL: &str = "There is no label with id";
+pub const ERROR_LABEL_ALREADY_EXISTS: &str = "Label with that name already exists";
+pub const ERROR_COULD_NOT_CREATE_LABEL: &str = "Could not create label";
+pub const ERROR_COULD_NOT_GET_LABELS: &str = "Could not get label(s)";
+pub const ERROR_COULD_NOT_UPDATE_LABEL: &str = "Could not update label with id";
+pub const ERROR_COULD_NOT_DELETE_LABEL: &str = "Could not delete label with id";
+pub const ERROR_UNKNOWN_STATUS: &str = "There is no status named";
+pub const ERROR_INVALID_TRANSITION: &str = "Status can't change from";
+pub const ERROR_STATUS_NOT_FOUND: &str = "Could not find status with id";
+pub const ERROR_STATUS_ALREADY_EXISTS: &str = "Status with that name already exists";
+pub const ERROR_STATUS_IN_USE: &str = "Status is still used by tickets";
+pub const ERROR_COULD_NOT_CREATE_STATUS: &str = "Could not create status";
+pub const ERROR_COULD_NOT_GET_STATUSES: &str = "Could not get status(es)";
+pub const ERROR_COULD_NOT_DELETE_STATUS: &str = "Could not delete status with id";
+pub const ERROR_TRANSITION_NOT_FOUND: &str = "Could not find transition with id";
+pub const ERROR_TRANSITION_ALREADY_EXISTS: &str = "Transition already exists";
+pub const ERROR_TRANSITION_TO_SAME_STATUS: &str = "A transition must lead to another status";
+pub const ERROR_COULD_NOT_CREATE_TRANSITION: &str = "Could not create transition";
+pub const ERROR_COULD_NOT_GET_TRANSITIONS: &str = "Could not get transition(s)";
+pub const ERROR_COULD_NOT_DELETE_TRANSITION: &str = "Could not delete transition with id";
+pub const ERROR_INVALID_DATE: &str = "Dates must be in the format YYYY-MM-DD, got";
+pub const ERROR_NEGATIVE_ESTIMATE: &str = "Estimates must not be negative, got";
+pub const ERROR_START_AFTER_DUE: &str = "Start date must not be after the due date";
+pub const ERROR_NOT_IN_TRASH: &str = "Could not find ticket in the trash with id";
+pub const ERROR_COULD_NOT_RESTORE: &str = "Could not restore ticket with id";
+pub const ERROR_COULD_NOT_PURGE: &str = "Could not purge ticket with id";
+pub const ERROR_NO_BULK_OPERATIONS: &str = "At least one operation must be given";
+pub const ERROR_INVALID_BULK_SELECTION: &str =
+    "Tickets must be selected either by ids or by a filter";
+pub const ERROR_COULD_NOT_UPDATE_TICKETS: &str = "Could not update tickets";
+pub const ERROR_UNKNOWN_TEMPLATE: &str = "There is no template with id";
+pub const ERROR_INVALID_TICKET: &str = "Invalid ticket:";
+pub const ERROR_TEMPLATE_NOT_FOUND: &str = "Could not find template with id";
+pub const ERROR_COULD_NOT_GET_TEMPLATES: &str = "Could not get template(s)";
+pub const ERROR_COULD_NOT_CREATE_TEMPLATE: &str = "Could not create template";
+pub const ERROR_COULD_NOT_UPDATE_TEMPLATE: &str = "Could not update template with id";
+pub const ERROR_COULD_NOT_DELETE_TEMPLATE: &str = "Could not delete template with id";
+pub const ERROR_VERSION_MISMATCH: &str =
+    "Ticket was changed in the meantime, reload ticket with id";
+pub const ERROR_INVALID_PATCH: &str = "Patch does not result in a valid ticket:";
+pub const ERROR_PROJECT_NOT_FOUND: &str = "Could not find project with key";
+pub const ERROR_PROJECT_ALREADY_EXISTS: &str = "Project with that key already exists";
+pub const ERROR_INVALID_PROJECT_KEY: &str =
+    "Project keys must be 2 to 10 uppercase letters or digits, starting with a letter";
+pub const ERROR_PROJECT_NOT_EMPTY: &str = "Project still has tickets";
+pub const ERROR_NO_PROJECT: &str = "There is no project to create the ticket in";
+pub const ERROR_COULD_NOT_CREATE_PROJECT: &str = "Could not create project";
+pub const ERROR_COULD_NOT_GET_PROJECTS: &str = "Could not get project(s)";
+pub const ERROR_COULD_NOT_DELETE_PROJECT: &str = "Could not delete project";
+pub const ERROR_MILESTONE_NOT_FOUND: &str = "Could not find milestone with id";
+pub const ERROR_UNKNOWN_MILESTONE: &str = "There is no milestone with id";
+pub const ERROR_MILESTONE_ENDS_BEFORE_START: &str = "Milestone must not end before it starts";
+pub const ERROR_COULD_NOT_CREATE_MILESTONE: &str = "Could not create milestone";
+pub const ERROR_COULD_NOT_GET_MILESTONES: &str = "Could not get milestone(s)";
+pub const ERROR_COULD_NOT_UPDATE_MILESTONE: &str = "Could not update milestone with id";
+pub const ERROR_COULD_NOT_DELETE_MILESTONE: &str = "Could not delete milestone with id";
+pub const ERROR_ATTACHMENT_NOT_FOUND: &str = "Could not find attachment with id";
+pub const ERROR_MISSING_ATTACHMENT_FILE: &str = "Upload must contain a field named file";
+pub const ERROR_INVALID_UPLOAD: &str = "Upload is not valid multipart form data";
+pub const ERROR_ATTACHMENT_TOO_LARGE: &str = "Attachments must not be larger than";
+pub const ERROR_NOT_ATTACHMENT_UPLOADER: &str = "Only the uploader can delete this attachment";
+pub const ERROR_COULD_NOT_CREATE_ATTACHMENT: &str = "Could not create attachment";
+pub const ERROR_COULD_NOT_GET_ATTACHMENTS: &str = "Could not get attachment(s)";
+pub const ERROR_COULD_NOT_DELETE_ATTACHMENT: &str = "Could not delete attachment with id";
+pub const ERROR_UNKNOWN_USER: &str = "There is no user with id";
+pub const ERROR_COULD_NOT_UPDATE_WATCHERS: &str = "Could not update watchers";
+pub const ERROR_LINK_NOT_FOUND: &str = "Could not find link with id";
+pub const ERROR_UNKNOWN_TICKET: &str = "There is no ticket with id";
+pub const ERROR_SELF_LINK: &str = "A ticket can't be linked to itself";
+pub const ERROR_ALREADY_LINKED: &str = "Tickets are already linked that way";
+pub const ERROR_ALREADY_HAS_PARENT: &str = "Ticket already has a parent";
+pub const ERROR_LINK_CYCLE: &str = "Link would create a cycle";
+pub const ERROR_COULD_NOT_CREATE_LINK: &str = "Could not create link";
+pub const ERROR_COULD_NOT_GET_LINKS: &str = "Could not get link(s)";
+pub const ERROR_COULD_NOT_DELETE_LINK: &str = "Could not delete link with id";
+pub const ERROR_COULD_NOT_GET_HISTORY: &str = "Could not get ticket history";
+pub const ERROR_COMMENT_NOT_FOUND: &str = "Could not find comment with id";
+pub const ERROR_COULD_NOT_CREATE_COMMENT: &str = "Could not create comment";
+pub const ERROR_COULD_NOT_GET_COMMENTS: &str = "Could not get comment(s)";
+pub const ERROR_COULD_NOT_UPDATE_COMMENT: &str = "Could not update comment with id";
+pub const ERROR_COULD_NOT_DELETE_COMMENT: &str = "Could not delete comment with id";
+pub const ERROR_INVALID_PARENT_COMMENT: &str = "Parent comment must belong to the same ticket";
+pub const ERROR_NOT_COMMENT_AUTHOR: &str = "Only the author can change this comment";
+pub const ERROR_WORKLOG_NOT_FOUND: &str = "Could not find worklog with id";
+pub const ERROR_COULD_NOT_GET_WORKLOGS: &str = "Could not get worklog(s)";
+pub const ERROR_COULD_NOT_CREATE_WORKLOG: &str = "Could not create worklog";
+pub const ERROR_COULD_NOT_UPDATE_WORKLOG: &str = "Could not update worklog with id";
+pub const ERROR_COULD_NOT_DELETE_WORKLOG: &str = "Could not delete worklog with id";
+pub const ERROR_NOT_WORKLOG_AUTHOR: &str = "Only the user who logged the work can change it";
+pub const ERROR_INVALID_MINUTES: &str = "Logged work must be at least one minute, got";
 
 // success messages
 pub const SUCCESS_LOGOUT: &str = "Successfully logged out";
+pub const ERROR_INVALID_DATE_RANGE: &str =
+    "The date range must start before it ends and span at most";
+pub const ERROR_COULD_NOT_GET_BURNDOWN: &str = "Could not compute burndown";
+pub const ERROR_COULD_NOT_GET_MENTIONS: &str = "Could not get mentions";
+pub const ERROR_EMPTY_SEARCH: &str = "Search query must not be empty";
+pub const ERROR_COULD_NOT_SEARCH: &str = "Could not search tickets";
+pub const ERROR_QUERY_MISSING_QUOTE: &str = "Missing closing quote";
+pub const ERROR_QUERY_MISSING_VALUE: &str = "Missing value for";
+pub const ERROR_QUERY_UNKNOWN_FIELD: &str = "Unknown field";
+pub const ERROR_QUERY_UNKNOWN_VALUE: &str = "Unknown value for";
+pub const ERROR_QUERY_INVALID_OPERATOR: &str = "Only `:` can be used with";
+pub const ERROR_QUERY_CANNOT_NEGATE: &str =
+    "Only label, status, assignee, is and words can be negated, got";
+pub const ERROR_QUERY_REPEATED_FIELD: &str = "Only one value is allowed for";
+pub const ERROR_QUERY_AMBIGUOUS_USER: &str = "Several users have the display name";
+pub const ERROR_SAVED_FILTER_NOT_FOUND: &str = "Could not find saved filter with id";
+pub const ERROR_COULD_NOT_GET_SAVED_FILTERS: &str = "Could not get saved filter(s)";
+pub const ERROR_COULD_NOT_CREATE_SAVED_FILTER: &str = "Could not create saved filter";
+pub const ERROR_COULD_NOT_UPDATE_SAVED_FILTER: &str = "Could not update saved filter with id";
+pub const ERROR_COULD_NOT_DELETE_SAVED_FILTER: &str = "Could not delete saved filter with id";
+pub const ERROR_NOT_SAVED_FILTER_OWNER: &str = "Only the owner of a saved filter can change it";
+pub const ERROR_EMPTY_FILTER_NAME: &str = "Saved filters must have a name";
+pub const ERROR_INVALID_SAVED_FILTER: &str = "Invalid filter:";
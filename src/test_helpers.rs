@@ -1,12 +1,14 @@
 #[cfg(test)]
 pub mod helpers {
+    use crate::attachments::storage_directory;
     use crate::database::DataBase;
-    use crate::models::{NewTicket, NewUser, Status};
+    use crate::models::{NewComment, NewTicket, NewUser, Priority, Severity, TokenClaims};
     use crate::schema::tickets::dsl::tickets;
-    use crate::schema::users;
-    use diesel::RunQueryDsl;
+    use crate::schema::{projects, ticket_assignees, ticket_comments, ticket_labels, users};
+    use actix_web::HttpMessage;
+    use diesel::{ExpressionMethods, RunQueryDsl};
     use dotenvy::dotenv;
-    use std::env;
+    use std::{env, fs};
 
     /**
      * Setup test database before each test to make sure tests don't depend on each other and always have the same state.
@@ -21,9 +23,19 @@ pub mod helpers {
             // moment as of writing this
             created: "1688587842815".to_string(),
             last_modified: "1688587842815".to_string(),
-            labels: "[\"Bug\", \"InProgress\"]".to_string(),
-            assigned_user: Some(1),
-            status: Status::Open.to_string(),
+            status: "Open".to_string(),
+            priority: Priority::Medium.rank(),
+            severity: Severity::Normal.to_string(),
+            start_date: None,
+            due_date: None,
+            // the default project is created by the migrations
+            project_id: 1,
+            key: "CIRA-1".to_string(),
+            milestone_id: None,
+            original_estimate: None,
+            remaining_estimate: None,
+            story_points: None,
+            reporter_id: None,
         };
         let test_user = NewUser {
             display_name: "user".to_string(),
@@ -36,18 +48,74 @@ pub mod helpers {
             .values(&test_ticket)
             .execute(&mut database.connection)
             .expect("Could not write test data into test database");
+        diesel::update(projects::table)
+            .set(projects::ticket_counter.eq(1))
+            .execute(&mut database.connection)
+            .expect("Could not update test project in test database");
         diesel::insert_into(users::table)
             .values(test_user)
             .execute(&mut database.connection)
             .expect("Could not write test user into test database");
+
+        diesel::insert_into(ticket_assignees::table)
+            .values((
+                ticket_assignees::ticket_id.eq(1),
+                ticket_assignees::user_id.eq(1),
+            ))
+            .execute(&mut database.connection)
+            .expect("Could not assign test ticket in test database");
+
+        // ids of "Bug" and "InProgress", labels are created by the migrations
+        diesel::insert_into(ticket_labels::table)
+            .values(&vec![
+                (
+                    ticket_labels::ticket_id.eq(1),
+                    ticket_labels::label_id.eq(2),
+                ),
+                (
+                    ticket_labels::ticket_id.eq(1),
+                    ticket_labels::label_id.eq(5),
+                ),
+            ])
+            .execute(&mut database.connection)
+            .expect("Could not label test ticket in test database");
+
+        let test_comment = NewComment {
+            ticket_id: 1,
+            author_id: 1,
+            parent_comment_id: None,
+            body: "Test Comment".to_string(),
+            created: "1688587842815".to_string(),
+            last_modified: "1688587842815".to_string(),
+        };
+
+        diesel::insert_into(ticket_comments::table)
+            .values(&test_comment)
+            .execute(&mut database.connection)
+            .expect("Could not write test comment into test database");
+    }
+
+    /**
+     * Handlers that need to know who is calling read the claims the bearer middleware puts into the request.
+     * Tests don't run the middleware, so this inserts them by hand.
+     **/
+    pub fn with_claims<R: HttpMessage>(request: R, user_id: i32) -> R {
+        request.extensions_mut().insert(TokenClaims {
+            id: user_id,
+            exp: u64::MAX,
+        });
+
+        request
     }
 
     pub fn reset_database() {
         dotenv().ok();
+        // attachment ids start over with the database, so their files have to go as well
+        fs::remove_dir_all(storage_directory()).ok();
         let database_url =
             env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL not set in .env");
         run_script::run_script!(format!(
-            "diesel migration redo --database-url {}",
+            "diesel migration redo --all --database-url {}",
             database_url
         ))
         .unwrap();